@@ -0,0 +1,171 @@
+use aoc_core::{parse_lines, parse_token, Answer, ParseError, Part};
+use std::path::PathBuf;
+
+use crate::solutions::NUM_DAYS;
+
+/// The known answer to one part of a day, for one puzzle input.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub day: usize,
+    pub part: Part,
+    pub input: PathBuf,
+    pub answer: Answer,
+}
+
+/// Parses an answers file, with one answer per line like
+/// `14 2 day_14/src/input.txt 2942885922173`.
+///
+/// Blank lines and lines starting with `#` are skipped. Text answers are
+/// written on one line, with newlines as `\n` and backslashes as `\\`.
+pub fn parse(answers: &str) -> Result<Vec<Entry>, ParseError> {
+    let entries = parse_lines(answers, parse_entry)?;
+    Ok(entries.into_iter().flatten().collect())
+}
+
+/// Writes an answer the way it appears in an answers file.
+pub fn escape(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(integer) => integer.to_string(),
+        Answer::Text(text) => text.replace('\\', "\\\\").replace('\n', "\\n"),
+    }
+}
+
+fn parse_entry(line: &str) -> Result<Option<Entry>, ParseError> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+
+    let mut rest = line;
+    let mut field = |expected| {
+        rest = rest.trim_start();
+        let (field, after) = rest.split_at(rest.find(char::is_whitespace).unwrap_or(rest.len()));
+        rest = after;
+        if field.is_empty() {
+            Err(ParseError::at(line, field, expected))
+        } else {
+            Ok(field)
+        }
+    };
+
+    let token = field("a day")?;
+    let day = parse_token(line, token, "a day")
+        .ok()
+        .filter(|day| (1..=NUM_DAYS).contains(day))
+        .ok_or_else(|| ParseError::at(line, token, format!("a day from 1 to {NUM_DAYS}")))?;
+    let token = field("a part")?;
+    let part = parse_token::<usize>(line, token, "a part")
+        .ok()
+        .and_then(|part| Part::try_from(part).ok())
+        .ok_or_else(|| ParseError::at(line, token, "part 1 or 2"))?;
+    let input = PathBuf::from(field("an input path")?);
+    let answer = rest.trim_start();
+    if answer.is_empty() {
+        return Err(ParseError::at(line, answer, "an answer"));
+    }
+
+    Ok(Some(Entry {
+        day,
+        part,
+        input,
+        answer: match answer.parse() {
+            Ok(integer) => Answer::Integer(integer),
+            Err(_) => Answer::Text(unescape(line, answer)?),
+        },
+    }))
+}
+
+fn unescape(line: &str, text: &str) -> Result<String, ParseError> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut characters = text.char_indices();
+    while let Some((index, character)) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some((_, 'n')) => unescaped.push('\n'),
+            Some((_, '\\')) => unescaped.push('\\'),
+            escape => {
+                let end = escape.map_or(text.len(), |(end, escaped)| end + escaped.len_utf8());
+                return Err(ParseError::at(line, &text[index..end], "`\\n` or `\\\\`"));
+            }
+        }
+    }
+    Ok(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(answers: &str) -> String {
+        parse(answers).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            "# comment\n\n2 1 day_02/src/commands.txt 2147104\n13  2  input.txt  #.\\n.#\\\\\\n\n";
+        assert_eq!(
+            parse(answers),
+            Ok(vec![
+                Entry {
+                    day: 2,
+                    part: Part::One,
+                    input: PathBuf::from("day_02/src/commands.txt"),
+                    answer: Answer::Integer(2_147_104),
+                },
+                Entry {
+                    day: 13,
+                    part: Part::Two,
+                    input: PathBuf::from("input.txt"),
+                    answer: Answer::Text("#.\n.#\\\n".to_owned()),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_error("1 1 input.txt 42\n19 1 input.txt 42\n"),
+            "line 2, column 1: expected a day from 1 to 18, found `19`"
+        );
+        assert_eq!(
+            parse_error("1 3 input.txt 42"),
+            "line 1, column 3: expected part 1 or 2, found `3`"
+        );
+        assert_eq!(
+            parse_error("1 1 input.txt"),
+            "line 1, column 14: expected an answer, found nothing"
+        );
+        assert_eq!(
+            parse_error("1 1 input.txt #\\t#"),
+            "line 1, column 16: expected `\\n` or `\\\\`, found `\\t`"
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        for answer in [Answer::Integer(-7), Answer::Text("#.\n\\#\n".to_owned())] {
+            let line = format!("13 2 input.txt {}", escape(&answer));
+            assert_eq!(parse(&line).unwrap()[0].answer, answer);
+        }
+    }
+
+    #[test]
+    fn test_answers_file() {
+        let entries = parse(include_str!("../../answers.txt")).unwrap();
+        for day in 1..=NUM_DAYS {
+            for part in [Part::One, Part::Two] {
+                assert!(
+                    entries
+                        .iter()
+                        .any(|entry| entry.day == day && entry.part == part),
+                    "no answer for day {day}, part {part}"
+                );
+            }
+        }
+    }
+}
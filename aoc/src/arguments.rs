@@ -0,0 +1,462 @@
+use aoc_core::Part;
+use std::{
+    fmt::{Display, Formatter, Result as FormatResult},
+    num::ParseIntError,
+    ops::RangeInclusive,
+    path::PathBuf,
+};
+
+use crate::{input::Input, solutions::NUM_DAYS};
+
+pub const USAGE: &str = "Usage:
+    aoc run --day <DAY> --part <PART> [--input <PATH>] [--json]
+    aoc bench [--day <DAY>] [--part <PART>] [--input <PATH>] [--iterations <N>] [--json]
+    aoc verify [--day <DAY>] [--answers <PATH>] [--json]
+    aoc generate --day <DAY> --size <SIZE> [--seed <SEED>]
+    aoc fuzz [--day <DAY>] [--iterations <N>] [--seed <SEED>]
+
+`run` reads the puzzle input from stdin when no input path (or `-`) is given.
+`bench` runs every day and part unless told otherwise, with the puzzle inputs
+in the repository, and 10 iterations by default.
+`verify` checks every answer in the answers file (the repository's `answers.txt`
+by default) is still what the solvers come up with.
+With `--json`, `run`, `bench` and `verify` print one JSON object instead of
+text, with times in nanoseconds and `null` for whatever is missing.
+`generate` prints a random puzzle input, which is the same every time for the
+same seed (0 by default). What the size means differs from day to day.
+`fuzz` feeds every day (unless told otherwise) randomly broken generated inputs,
+10000 by default, solving the short ones that parse, and prints the smallest
+input that made one panic.";
+
+const MAX_ITERATIONS: usize = 1_000_000;
+const MAX_SIZE: usize = 10_000_000;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    Run {
+        day: usize,
+        part: Part,
+        input: Input,
+        json: bool,
+    },
+    Bench {
+        day: Option<usize>,
+        part: Option<Part>,
+        input: Option<Input>,
+        iterations: usize,
+        json: bool,
+    },
+    Verify {
+        day: Option<usize>,
+        answers: Option<PathBuf>,
+        json: bool,
+    },
+    Generate {
+        day: usize,
+        size: usize,
+        seed: u64,
+    },
+    Fuzz {
+        day: Option<usize>,
+        iterations: usize,
+        seed: u64,
+    },
+}
+
+impl Command {
+    pub fn parse(mut arguments: impl Iterator<Item = String>) -> Result<Self, ArgumentError> {
+        match arguments.next().as_deref() {
+            Some("run") => parse_run(arguments),
+            Some("bench") => parse_bench(arguments),
+            Some("verify") => parse_verify(arguments),
+            Some("generate") => parse_generate(arguments),
+            Some("fuzz") => parse_fuzz(arguments),
+            Some(command) => Err(ArgumentError::UnknownCommand(command.to_owned())),
+            None => Err(ArgumentError::MissingCommand),
+        }
+    }
+}
+
+fn parse_run(mut arguments: impl Iterator<Item = String>) -> Result<Command, ArgumentError> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut json = false;
+
+    while let Some(flag) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or(ArgumentError::MissingValue(flag.clone()))
+        };
+        match flag.as_str() {
+            "--day" => day = Some(parse_number(&value()?, 1..=NUM_DAYS, "--day")?),
+            "--part" => {
+                let number = parse_number(&value()?, 1..=2, "--part")?;
+                part = Part::try_from(number).ok();
+            }
+            "--input" => input = Some(Input::from(value()?)),
+            "--json" => json = true,
+            _ => return Err(ArgumentError::UnknownFlag(flag)),
+        }
+    }
+
+    Ok(Command::Run {
+        day: day.ok_or(ArgumentError::MissingFlag("--day"))?,
+        part: part.ok_or(ArgumentError::MissingFlag("--part"))?,
+        input: input.unwrap_or(Input::Stdin),
+        json,
+    })
+}
+
+fn parse_bench(mut arguments: impl Iterator<Item = String>) -> Result<Command, ArgumentError> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut iterations = 10;
+    let mut json = false;
+
+    while let Some(flag) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or(ArgumentError::MissingValue(flag.clone()))
+        };
+        match flag.as_str() {
+            "--day" => day = Some(parse_number(&value()?, 1..=NUM_DAYS, "--day")?),
+            "--part" => {
+                let number = parse_number(&value()?, 1..=2, "--part")?;
+                part = Part::try_from(number).ok();
+            }
+            "--input" => input = Some(Input::from(value()?)),
+            "--iterations" => {
+                iterations = parse_number(&value()?, 1..=MAX_ITERATIONS, "--iterations")?;
+            }
+            "--json" => json = true,
+            _ => return Err(ArgumentError::UnknownFlag(flag)),
+        }
+    }
+
+    // Every day has a different kind of input
+    if input.is_some() && day.is_none() {
+        return Err(ArgumentError::MissingFlag("--day"));
+    }
+
+    Ok(Command::Bench {
+        day,
+        part,
+        input,
+        iterations,
+        json,
+    })
+}
+
+fn parse_verify(mut arguments: impl Iterator<Item = String>) -> Result<Command, ArgumentError> {
+    let mut day = None;
+    let mut answers = None;
+    let mut json = false;
+
+    while let Some(flag) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or(ArgumentError::MissingValue(flag.clone()))
+        };
+        match flag.as_str() {
+            "--day" => day = Some(parse_number(&value()?, 1..=NUM_DAYS, "--day")?),
+            "--answers" => answers = Some(PathBuf::from(value()?)),
+            "--json" => json = true,
+            _ => return Err(ArgumentError::UnknownFlag(flag)),
+        }
+    }
+
+    Ok(Command::Verify { day, answers, json })
+}
+
+fn parse_generate(mut arguments: impl Iterator<Item = String>) -> Result<Command, ArgumentError> {
+    let mut day = None;
+    let mut size = None;
+    let mut seed = 0;
+
+    while let Some(flag) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or(ArgumentError::MissingValue(flag.clone()))
+        };
+        match flag.as_str() {
+            "--day" => day = Some(parse_number(&value()?, 1..=NUM_DAYS, "--day")?),
+            "--size" => size = Some(parse_number(&value()?, 1..=MAX_SIZE, "--size")?),
+            "--seed" => seed = parse_seed(&value()?)?,
+            _ => return Err(ArgumentError::UnknownFlag(flag)),
+        }
+    }
+
+    Ok(Command::Generate {
+        day: day.ok_or(ArgumentError::MissingFlag("--day"))?,
+        size: size.ok_or(ArgumentError::MissingFlag("--size"))?,
+        seed,
+    })
+}
+
+fn parse_fuzz(mut arguments: impl Iterator<Item = String>) -> Result<Command, ArgumentError> {
+    let mut day = None;
+    let mut iterations = 10_000;
+    let mut seed = 0;
+
+    while let Some(flag) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or(ArgumentError::MissingValue(flag.clone()))
+        };
+        match flag.as_str() {
+            "--day" => day = Some(parse_number(&value()?, 1..=NUM_DAYS, "--day")?),
+            "--iterations" => {
+                iterations = parse_number(&value()?, 1..=MAX_ITERATIONS, "--iterations")?;
+            }
+            "--seed" => seed = parse_seed(&value()?)?,
+            _ => return Err(ArgumentError::UnknownFlag(flag)),
+        }
+    }
+
+    Ok(Command::Fuzz {
+        day,
+        iterations,
+        seed,
+    })
+}
+
+fn parse_seed(value: &str) -> Result<u64, ArgumentError> {
+    value
+        .parse()
+        .map_err(|error| ArgumentError::InvalidNumber("--seed", error))
+}
+
+fn parse_number(
+    value: &str,
+    range: RangeInclusive<usize>,
+    flag: &'static str,
+) -> Result<usize, ArgumentError> {
+    let number = value
+        .parse()
+        .map_err(|error| ArgumentError::InvalidNumber(flag, error))?;
+    if range.contains(&number) {
+        Ok(number)
+    } else {
+        Err(ArgumentError::OutOfRange(flag, number, range))
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ArgumentError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownFlag(String),
+    MissingFlag(&'static str),
+    MissingValue(String),
+    InvalidNumber(&'static str, ParseIntError),
+    OutOfRange(&'static str, usize, RangeInclusive<usize>),
+}
+
+impl Display for ArgumentError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::MissingCommand => write!(formatter, "missing command"),
+            Self::UnknownCommand(command) => write!(formatter, "unknown command `{command}`"),
+            Self::UnknownFlag(flag) => write!(formatter, "unknown flag `{flag}`"),
+            Self::MissingFlag(flag) => write!(formatter, "missing required flag `{flag}`"),
+            Self::MissingValue(flag) => write!(formatter, "flag `{flag}` requires a value"),
+            Self::InvalidNumber(flag, error) => write!(formatter, "invalid `{flag}`: {error}"),
+            Self::OutOfRange(flag, number, range) => write!(
+                formatter,
+                "`{flag}` must be between {} and {}, got {number}",
+                range.start(),
+                range.end()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(arguments: &str) -> Result<Command, ArgumentError> {
+        Command::parse(arguments.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse("run --day 14 --part 2 --input input.txt"),
+            Ok(Command::Run {
+                day: 14,
+                part: Part::Two,
+                input: Input::File(PathBuf::from("input.txt")),
+                json: false
+            })
+        );
+        assert_eq!(
+            parse("run --input input.txt --json --part 1 --day 3"),
+            Ok(Command::Run {
+                day: 3,
+                part: Part::One,
+                input: Input::File(PathBuf::from("input.txt")),
+                json: true
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_run_from_stdin() {
+        for arguments in ["run --day 1 --part 2", "run --day 1 --part 2 --input -"] {
+            assert_eq!(
+                parse(arguments),
+                Ok(Command::Run {
+                    day: 1,
+                    part: Part::Two,
+                    input: Input::Stdin,
+                    json: false
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse("bench"),
+            Ok(Command::Bench {
+                day: None,
+                part: None,
+                input: None,
+                iterations: 10,
+                json: false
+            })
+        );
+        assert_eq!(
+            parse("bench --json --day 15 --part 2 --iterations 3 --input input.txt"),
+            Ok(Command::Bench {
+                day: Some(15),
+                part: Some(Part::Two),
+                input: Some(Input::File(PathBuf::from("input.txt"))),
+                iterations: 3,
+                json: true
+            })
+        );
+        assert_eq!(
+            parse("bench --input input.txt"),
+            Err(ArgumentError::MissingFlag("--day"))
+        );
+        assert_eq!(
+            parse("bench --iterations 0"),
+            Err(ArgumentError::OutOfRange(
+                "--iterations",
+                0,
+                1..=MAX_ITERATIONS
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            parse("verify"),
+            Ok(Command::Verify {
+                day: None,
+                answers: None,
+                json: false
+            })
+        );
+        assert_eq!(
+            parse("verify --answers answers.txt --day 7 --json"),
+            Ok(Command::Verify {
+                day: Some(7),
+                answers: Some(PathBuf::from("answers.txt")),
+                json: true
+            })
+        );
+        assert_eq!(
+            parse("verify --part 1"),
+            Err(ArgumentError::UnknownFlag("--part".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        assert_eq!(
+            parse("generate --day 16 --size 500"),
+            Ok(Command::Generate {
+                day: 16,
+                size: 500,
+                seed: 0
+            })
+        );
+        assert_eq!(
+            parse("generate --seed 18446744073709551615 --size 1 --day 1"),
+            Ok(Command::Generate {
+                day: 1,
+                size: 1,
+                seed: u64::MAX
+            })
+        );
+        assert_eq!(
+            parse("generate --day 16"),
+            Err(ArgumentError::MissingFlag("--size"))
+        );
+        assert!(matches!(
+            parse("generate --day 1 --size 1 --seed -1"),
+            Err(ArgumentError::InvalidNumber("--seed", _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        assert_eq!(
+            parse("fuzz"),
+            Ok(Command::Fuzz {
+                day: None,
+                iterations: 10_000,
+                seed: 0
+            })
+        );
+        assert_eq!(
+            parse("fuzz --seed 7 --iterations 50 --day 10"),
+            Ok(Command::Fuzz {
+                day: Some(10),
+                iterations: 50,
+                seed: 7
+            })
+        );
+        assert_eq!(
+            parse("fuzz --size 5"),
+            Err(ArgumentError::UnknownFlag("--size".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse(""), Err(ArgumentError::MissingCommand));
+        assert_eq!(
+            parse("walk"),
+            Err(ArgumentError::UnknownCommand("walk".to_owned()))
+        );
+        assert_eq!(
+            parse("run --day 3 --input input.txt"),
+            Err(ArgumentError::MissingFlag("--part"))
+        );
+        assert_eq!(
+            parse("run --day"),
+            Err(ArgumentError::MissingValue("--day".to_owned()))
+        );
+        assert_eq!(
+            parse("run --day 26 --part 1 --input input.txt"),
+            Err(ArgumentError::OutOfRange("--day", 26, 1..=NUM_DAYS))
+        );
+        assert_eq!(
+            parse("run --verbose"),
+            Err(ArgumentError::UnknownFlag("--verbose".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use aoc_core::{Answer, Json, Part, Statistics};
+use std::fmt::{Display, Formatter, Result as FormatResult};
+
+use crate::solutions;
+
+/// Timings of solving one part of one day, over a number of iterations.
+pub struct Benchmark {
+    pub day: usize,
+    pub part: Part,
+    pub answer: Answer,
+    pub iterations: usize,
+    pub parse: Statistics,
+    pub solve: Statistics,
+}
+
+impl Benchmark {
+    /// Solves the puzzle once to warm up (and get the answer), and then
+    /// `iterations` more times to measure.
+    pub fn run(day: usize, part: Part, input: &str, iterations: usize) -> Result<Self, String> {
+        let solver = solutions::solver(day);
+        let answer = solver(input, part)?.answer;
+
+        let mut parse_times = Vec::with_capacity(iterations);
+        let mut solve_times = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let timed = solver(input, part)?;
+            parse_times.push(timed.parse_time);
+            solve_times.push(timed.solve_time);
+        }
+
+        Ok(Self {
+            day,
+            part,
+            answer,
+            iterations,
+            parse: Statistics::of(&parse_times),
+            solve: Statistics::of(&solve_times),
+        })
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("day", Json::from(self.day)),
+            ("part", Json::from(usize::from(self.part))),
+            ("answer", Json::from(&self.answer)),
+            ("iterations", Json::from(self.iterations)),
+            ("parse", self.parse.to_json()),
+            ("solve", self.solve.to_json()),
+        ])
+    }
+}
+
+impl Display for Benchmark {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(
+            formatter,
+            "Day {:>2}, part {}: parse {}; solve {}",
+            self.day, self.part, self.parse, self.solve
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let benchmark = Benchmark::run(1, Part::Two, "1\n2\n3\n4\n", 3).unwrap();
+        assert_eq!(benchmark.answer, Answer::Integer(1));
+        assert_eq!(benchmark.iterations, 3);
+        assert!(Benchmark::run(1, Part::One, "x", 3).is_err());
+    }
+}
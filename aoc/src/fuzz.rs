@@ -0,0 +1,72 @@
+use aoc_core::{Part, Rng};
+
+use crate::solutions;
+
+// Sizes of the generated inputs the mutations start from, small enough that
+// the shrunk inputs stay readable
+const SIZES: [usize; 4] = [1, 2, 5, 20];
+
+// Solving takes much longer than parsing, so only inputs up to this long are
+// solved, not just parsed
+const MAX_SOLVED_LENGTH: usize = 100;
+
+/// Feeds `day` `iterations` mutated versions of generated inputs, solving
+/// both parts of the short ones that parse, and returns the smallest input
+/// found that makes it panic.
+///
+/// The mutations stay close to small generated inputs, so finding nothing says
+/// little about inputs far from them, like deep nesting, very long lines or
+/// numbers near a type's limit.
+pub fn run(day: usize, rng: &mut Rng, iterations: usize) -> Option<String> {
+    let generate = solutions::generator(day);
+    let parse = solutions::parser(day);
+    let solve = solutions::solver(day);
+    let inputs: Vec<_> = SIZES.iter().map(|&size| generate(rng, size)).collect();
+    aoc_core::find_panic(rng, &inputs, iterations, |input| {
+        if parse(input).is_ok() && input.len() <= MAX_SOLVED_LENGTH {
+            for part in [Part::One, Part::Two] {
+                // Errors are fine, only panics are interesting
+                drop(solve(input, part));
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::NUM_DAYS;
+
+    #[test]
+    fn test_run_finds_no_panics() {
+        for day in 1..=NUM_DAYS {
+            let panic = run(day, &mut Rng::new(0), 2_000);
+            assert_eq!(panic, None, "day {day} panics");
+        }
+    }
+
+    // Inputs that used to panic or overflow the stack, which are too far from
+    // generated inputs for run to find
+    #[test]
+    fn test_known_panics() {
+        let inputs = [
+            (10, "<".repeat(28)),
+            // 200 000 sums, each of just the next one, around a literal 0
+            (16, "020040801".repeat(100_000) + "100"),
+            // A sum of two literals of 2^63
+            (
+                16,
+                "020084C42108421084210842001310842108421084210800".to_owned(),
+            ),
+            (18, "[".repeat(300_000)),
+            (18, "[[[[[1,2],3],4],5],6]\n[1,1]".to_owned()),
+            (18, "[4294967295,1]".to_owned()),
+        ];
+        for (day, input) in inputs {
+            for part in [Part::One, Part::Two] {
+                // Errors are fine, only panics are interesting
+                drop(solutions::solver(day)(&input, part));
+            }
+        }
+    }
+}
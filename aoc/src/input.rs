@@ -0,0 +1,46 @@
+use std::{
+    fmt::{Display, Formatter, Result as FormatResult},
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+/// Where to read a puzzle input from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Self::File(path) => fs::read_to_string(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+// Like most command line tools, `-` means stdin.
+impl From<String> for Input {
+    fn from(argument: String) -> Self {
+        if argument == "-" {
+            Self::Stdin
+        } else {
+            Self::File(PathBuf::from(argument))
+        }
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::File(path) => write!(formatter, "{}", path.display()),
+            Self::Stdin => write!(formatter, "stdin"),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+mod answers;
+mod arguments;
+mod bench;
+mod fuzz;
+mod input;
+mod solutions;
+mod verify;
+
+use aoc_core::{Answer, Json, Part, Rng};
+use arguments::{Command, USAGE};
+use bench::Benchmark;
+use input::Input;
+use solutions::NUM_DAYS;
+use std::{env, fs, path::Path, process::ExitCode};
+use verify::{Outcome, Verification};
+
+fn main() -> ExitCode {
+    match Command::parse(env::args().skip(1)) {
+        Ok(Command::Run {
+            day,
+            part,
+            input,
+            json,
+        }) => run(day, part, &input, json),
+        Ok(Command::Bench {
+            day,
+            part,
+            input,
+            iterations,
+            json,
+        }) => bench(day, part, input.as_ref(), iterations, json),
+        Ok(Command::Verify { day, answers, json }) => {
+            verify(day, &answers.unwrap_or_else(solutions::answers_file), json)
+        }
+        Ok(Command::Generate { day, size, seed }) => {
+            print!("{}", solutions::generator(day)(&mut Rng::new(seed), size));
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Fuzz {
+            day,
+            iterations,
+            seed,
+        }) => fuzz(day, iterations, seed),
+        Err(error) => {
+            eprintln!("error: {error}\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(day: usize, part: Part, input: &Input, json: bool) -> ExitCode {
+    let solved = input
+        .read()
+        .map_err(|error| format!("could not read {input}: {error}"))
+        .and_then(|contents| solutions::solver(day)(&contents, part));
+
+    match (&solved, json) {
+        (_, true) => println!("{}", solutions::to_json(day, part, input, &solved)),
+        (Ok(solved), false) => {
+            match &solved.answer {
+                Answer::Text(text) => println!("Day {day}, part {part}:\n{}", text.trim_end()),
+                answer @ Answer::Integer(_) => println!("Day {day}, part {part}: {answer}"),
+            }
+            println!(
+                "Time: {:?} (parse {:?}, solve {:?})",
+                solved.parse_time + solved.solve_time,
+                solved.parse_time,
+                solved.solve_time
+            );
+        }
+        (Err(error), false) => eprintln!("error: day {day}, part {part} failed: {error}"),
+    }
+    if solved.is_ok() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn bench(
+    day: Option<usize>,
+    part: Option<Part>,
+    input: Option<&Input>,
+    iterations: usize,
+    json: bool,
+) -> ExitCode {
+    let days = day.map_or(1..=NUM_DAYS, |day| day..=day);
+    let parts = part.map_or(vec![Part::One, Part::Two], |part| vec![part]);
+    let mut results = Vec::new();
+    let mut succeeded = true;
+
+    for day in days {
+        let input = input.map_or_else(|| solutions::puzzle_input(day), Input::clone);
+        let result = input
+            .read()
+            .map_err(|error| format!("could not read {input}: {error}"))
+            .and_then(|input| {
+                parts
+                    .iter()
+                    .map(|&part| Benchmark::run(day, part, &input, iterations))
+                    .collect::<Result<Vec<_>, _>>()
+            });
+
+        match (result, json) {
+            (Ok(benchmarks), false) => {
+                for benchmark in benchmarks {
+                    println!("{benchmark}");
+                }
+            }
+            (Ok(benchmarks), true) => results.extend(benchmarks.iter().map(Benchmark::to_json)),
+            (Err(error), false) => {
+                eprintln!("error: day {day} failed: {error}");
+                succeeded = false;
+            }
+            (Err(error), true) => {
+                results.push(Json::Object(vec![
+                    ("day", Json::from(day)),
+                    ("error", Json::from(error.as_str())),
+                ]));
+                succeeded = false;
+            }
+        }
+    }
+
+    if json {
+        println!(
+            "{}",
+            Json::Object(vec![("benchmarks", Json::Array(results))])
+        );
+    }
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn verify(day: Option<usize>, path: &Path, json: bool) -> ExitCode {
+    let entries = match fs::read_to_string(path) {
+        Ok(answers) => answers::parse(&answers),
+        Err(error) => {
+            eprintln!("error: could not read {}: {error}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("error: invalid answers file {}: {error}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Inputs are relative to the answers file
+    let directory = path.parent().unwrap_or(Path::new("."));
+    let (mut passed, mut mismatched, mut failed) = (0, 0, 0);
+    let mut results = Vec::new();
+    for entry in entries
+        .iter()
+        .filter(|entry| day.is_none_or(|day| entry.day == day))
+    {
+        let verification = Verification::run(entry, directory);
+        if json {
+            results.push(verification.to_json());
+        } else {
+            println!("{verification}");
+        }
+        match verification.outcome {
+            Outcome::Pass => passed += 1,
+            Outcome::Mismatch(_) => mismatched += 1,
+            Outcome::Fail(_) => failed += 1,
+        }
+    }
+
+    if json {
+        println!(
+            "{}",
+            Json::Object(vec![
+                ("verifications", Json::Array(results)),
+                ("passed", Json::from(passed)),
+                ("mismatched", Json::from(mismatched)),
+                ("failed", Json::from(failed)),
+            ])
+        );
+    } else {
+        println!("{passed} passed, {mismatched} mismatched, {failed} failed");
+    }
+    if mismatched + failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn fuzz(day: Option<usize>, iterations: usize, seed: u64) -> ExitCode {
+    let days = day.map_or(1..=NUM_DAYS, |day| day..=day);
+    let mut rng = Rng::new(seed);
+    let mut succeeded = true;
+
+    for day in days {
+        match fuzz::run(day, &mut rng, iterations) {
+            None => println!("Day {day:>2}: no panics in {iterations} inputs"),
+            Some(input) => {
+                println!("Day {day:>2}: PANIC on input {input:?}");
+                succeeded = false;
+            }
+        }
+    }
+
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
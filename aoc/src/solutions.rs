@@ -0,0 +1,235 @@
+use aoc_core::{Answer, Json, Part, Rng, Solution};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::input::Input;
+
+pub const NUM_DAYS: usize = 18;
+
+pub type Solver = fn(&str, Part) -> Result<Solved, String>;
+
+// Indexed by day - 1.
+pub const SOLVERS: [Solver; NUM_DAYS] = [
+    solve::<day_01::Day01>,
+    solve::<day_02::Day02>,
+    solve::<day_03::Day03>,
+    solve::<day_04::Day04>,
+    solve::<day_05::Day05>,
+    solve::<day_06::Day06>,
+    solve::<day_07::Day07>,
+    solve::<day_08::Day08>,
+    solve::<day_09::Day09>,
+    solve::<day_10::Day10>,
+    solve::<day_11::Day11>,
+    solve::<day_12::Day12>,
+    solve::<day_13::Day13>,
+    solve::<day_14::Day14>,
+    solve::<day_15::Day15>,
+    solve::<day_16::Day16>,
+    solve::<day_17::Day17>,
+    solve::<day_18::Day18>,
+];
+
+pub type Parser = fn(&str) -> Result<(), String>;
+
+// Indexed by day - 1.
+pub const PARSERS: [Parser; NUM_DAYS] = [
+    parse::<day_01::Day01>,
+    parse::<day_02::Day02>,
+    parse::<day_03::Day03>,
+    parse::<day_04::Day04>,
+    parse::<day_05::Day05>,
+    parse::<day_06::Day06>,
+    parse::<day_07::Day07>,
+    parse::<day_08::Day08>,
+    parse::<day_09::Day09>,
+    parse::<day_10::Day10>,
+    parse::<day_11::Day11>,
+    parse::<day_12::Day12>,
+    parse::<day_13::Day13>,
+    parse::<day_14::Day14>,
+    parse::<day_15::Day15>,
+    parse::<day_16::Day16>,
+    parse::<day_17::Day17>,
+    parse::<day_18::Day18>,
+];
+
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+// Indexed by day - 1.
+pub const GENERATORS: [Generator; NUM_DAYS] = [
+    day_01::generate,
+    day_02::generate,
+    day_03::generate,
+    day_04::generate,
+    day_05::generate,
+    day_06::generate,
+    day_07::generate,
+    day_08::generate,
+    day_09::generate,
+    day_10::generate,
+    day_11::generate,
+    day_12::generate,
+    day_13::generate,
+    day_14::generate,
+    day_15::generate,
+    day_16::generate,
+    day_17::generate,
+    day_18::generate,
+];
+
+// Indexed by day - 1, relative to the workspace root.
+const PUZZLE_INPUTS: [&str; NUM_DAYS] = [
+    "day_01/src/depth-measurements.txt",
+    "day_02/src/commands.txt",
+    "day_03/src/input.txt",
+    "day_04/src/input.txt",
+    "day_05/src/input.txt",
+    "day_06/src/input.txt",
+    "day_07/src/input.txt",
+    "day_08/src/input.txt",
+    "day_09/src/input.txt",
+    "day_10/src/input.txt",
+    "day_11/src/input.txt",
+    "day_12/src/input.txt",
+    "day_13/src/input.txt",
+    "day_14/src/input.txt",
+    "day_15/src/input.txt",
+    "day_16/src/input.txt",
+    "day_17/src/input.txt",
+    "day_18/src/input.txt",
+];
+
+pub fn solver(day: usize) -> Solver {
+    SOLVERS[day - 1]
+}
+
+pub fn parser(day: usize) -> Parser {
+    PARSERS[day - 1]
+}
+
+pub fn generator(day: usize) -> Generator {
+    GENERATORS[day - 1]
+}
+
+/// The puzzle input checked into the repository for `day`.
+pub fn puzzle_input(day: usize) -> Input {
+    Input::File(workspace().join(PUZZLE_INPUTS[day - 1]))
+}
+
+/// The answers file checked into the repository.
+pub fn answers_file() -> PathBuf {
+    workspace().join("answers.txt")
+}
+
+fn workspace() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc is a member of the workspace")
+}
+
+pub struct Solved {
+    pub answer: Answer,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// What solving part of a day on `input` came to, with `null` answer and
+/// times if it failed, and a `null` error if it didn't.
+pub fn to_json(day: usize, part: Part, input: &Input, solved: &Result<Solved, String>) -> Json {
+    let (answer, parse_time, solve_time, error) = match solved {
+        Ok(solved) => (
+            Json::from(&solved.answer),
+            Json::from(solved.parse_time),
+            Json::from(solved.solve_time),
+            Json::Null,
+        ),
+        Err(error) => (
+            Json::Null,
+            Json::Null,
+            Json::Null,
+            Json::from(error.as_str()),
+        ),
+    };
+    Json::Object(vec![
+        ("day", Json::from(day)),
+        ("part", Json::from(usize::from(part))),
+        ("input", Json::from(input.to_string().as_str())),
+        ("answer", answer),
+        ("parse_ns", parse_time),
+        ("solve_ns", solve_time),
+        ("error", error),
+    ])
+}
+
+// Only parses, for when solving could take too long (like on large random
+// inputs)
+fn parse<S: Solution>(input: &str) -> Result<(), String> {
+    S::parse(input).map(drop).map_err(|error| error.to_string())
+}
+
+fn solve<S: Solution>(input: &str, part: Part) -> Result<Solved, String> {
+    let start = Instant::now();
+    let input = S::parse(input).map_err(|error| error.to_string())?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = match part {
+        Part::One => S::part_1(&input),
+        Part::Two => S::part_2(&input),
+    }
+    .map_err(|error| error.to_string())?;
+    let solve_time = start.elapsed();
+
+    Ok(Solved {
+        answer,
+        parse_time,
+        solve_time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let solved = Ok(Solved {
+            answer: Answer::Integer(7),
+            parse_time: Duration::from_nanos(5),
+            solve_time: Duration::from_nanos(20),
+        });
+        assert_eq!(
+            to_json(1, Part::Two, &Input::Stdin, &solved).to_string(),
+            r#"{"day":1,"part":2,"input":"stdin","answer":7,"parse_ns":5,"solve_ns":20,"error":null}"#
+        );
+
+        let input = Input::File(PathBuf::from("packet.txt"));
+        assert_eq!(
+            to_json(16, Part::One, &input, &solver(16)("G", Part::One)).to_string(),
+            r#"{"day":16,"part":1,"input":"packet.txt","answer":null,"parse_ns":null,"solve_ns":null,"error":"line 1, column 1: expected a hexadecimal digit, found `G`"}"#
+        );
+    }
+
+    #[test]
+    fn test_generators() {
+        for day in 1..=NUM_DAYS {
+            let generate = generator(day);
+            let input = generate(&mut Rng::new(1), 10);
+            assert_eq!(input, generate(&mut Rng::new(1), 10), "day {day}");
+            assert_ne!(input, generate(&mut Rng::new(2), 10), "day {day}");
+            for size in 1..=10 {
+                for seed in 0..3 {
+                    let input = generate(&mut Rng::new(seed), size);
+                    for part in [Part::One, Part::Two] {
+                        if let Err(error) = solver(day)(&input, part) {
+                            panic!("day {day}, size {size}, seed {seed}: {error}\n{input}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use aoc_core::{Answer, Json};
+use std::{
+    fmt::{Display, Formatter, Result as FormatResult},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{
+    answers::{self, Entry},
+    input::Input,
+    solutions::{self, Solved},
+};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Mismatch(Answer),
+    Fail(String),
+}
+
+impl Outcome {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Mismatch(_) => "mismatch",
+            Self::Fail(_) => "fail",
+        }
+    }
+}
+
+/// The result of solving one entry of the answers file.
+pub struct Verification<'a> {
+    pub entry: &'a Entry,
+    pub input: Input,
+    pub outcome: Outcome,
+    pub solved: Result<Solved, String>,
+}
+
+impl<'a> Verification<'a> {
+    /// Solves `entry`, with its input path relative to `directory`.
+    pub fn run(entry: &'a Entry, directory: &Path) -> Self {
+        let input = Input::File(directory.join(&entry.input));
+        let solved = input
+            .read()
+            .map_err(|error| format!("could not read {input}: {error}"))
+            .and_then(|input| solutions::solver(entry.day)(&input, entry.part));
+
+        let outcome = match &solved {
+            Ok(solved) if solved.answer == entry.answer => Outcome::Pass,
+            Ok(solved) => Outcome::Mismatch(solved.answer.clone()),
+            Err(error) => Outcome::Fail(error.clone()),
+        };
+        Self {
+            entry,
+            input,
+            outcome,
+            solved,
+        }
+    }
+
+    pub fn time(&self) -> Duration {
+        self.solved.as_ref().map_or(Duration::ZERO, |solved| {
+            solved.parse_time + solved.solve_time
+        })
+    }
+
+    pub fn to_json(&self) -> Json {
+        let mut json =
+            solutions::to_json(self.entry.day, self.entry.part, &self.input, &self.solved);
+        if let Json::Object(fields) = &mut json {
+            fields.push(("expected", Json::from(&self.entry.answer)));
+            fields.push(("outcome", Json::from(self.outcome.name())));
+        }
+        json
+    }
+}
+
+impl Display for Verification<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        let Entry {
+            day, part, input, ..
+        } = self.entry;
+        write!(
+            formatter,
+            "Day {day:>2}, part {part} ({}): ",
+            input.display()
+        )?;
+        match &self.outcome {
+            Outcome::Pass => write!(formatter, "pass in {:.2?}", self.time()),
+            Outcome::Mismatch(answer) => write!(
+                formatter,
+                "MISMATCH in {:.2?}, expected {} but got {}",
+                self.time(),
+                answers::escape(&self.entry.answer),
+                answers::escape(answer)
+            ),
+            Outcome::Fail(error) => write!(formatter, "FAIL, {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Part;
+    use std::path::PathBuf;
+
+    fn entry(input: &str, answer: i128) -> Entry {
+        Entry {
+            day: 1,
+            part: Part::One,
+            input: PathBuf::from(input),
+            answer: Answer::Integer(answer),
+        }
+    }
+
+    fn workspace() -> &'static Path {
+        Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+    }
+
+    fn verify(input: &str, answer: i128) -> Outcome {
+        Verification::run(&entry(input, answer), workspace()).outcome
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(
+            verify("day_01/src/depth-measurements.txt", 1_754),
+            Outcome::Pass
+        );
+        assert_eq!(
+            verify("day_01/src/depth-measurements.txt", 1_755),
+            Outcome::Mismatch(Answer::Integer(1_754))
+        );
+        assert!(matches!(
+            verify("day_01/src/missing.txt", 1_754),
+            Outcome::Fail(error) if error.starts_with("could not read")
+        ));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let missing = entry("day_01/src/missing.txt", 1_754);
+        let json = Verification::run(&missing, Path::new("/nowhere")).to_json();
+        assert_eq!(
+            json.to_string(),
+            "{\"day\":1,\"part\":1,\"input\":\"/nowhere/day_01/src/missing.txt\",\"answer\":null,\
+             \"parse_ns\":null,\"solve_ns\":null,\"error\":\"could not read \
+             /nowhere/day_01/src/missing.txt: No such file or directory (os error 2)\",\
+             \"expected\":1754,\"outcome\":\"fail\"}"
+        );
+
+        let passing = entry("day_01/src/depth-measurements.txt", 1_754);
+        let json = Verification::run(&passing, workspace())
+            .to_json()
+            .to_string();
+        assert!(json.contains("\"answer\":1754,\"parse_ns\":"), "{json}");
+        assert!(json.ends_with("\"error\":null,\"expected\":1754,\"outcome\":\"pass\"}"));
+    }
+}
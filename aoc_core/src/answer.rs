@@ -0,0 +1,75 @@
+use std::fmt::{Display, Formatter, Result as FormatResult};
+
+/// The answer to one part of a puzzle.
+///
+/// Most puzzles have a number as their answer, but some (like the letters
+/// drawn by the folds of day 13) have to be read from multi-line text.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(integer: $integer) -> Self {
+                    Self::Integer(i128::from(integer))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(integer: usize) -> Self {
+        Self::Integer(i128::try_from(integer).expect("usize is at most 64 bits wide"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Integer(integer) => write!(formatter, "{integer}"),
+            Self::Text(text) => write!(formatter, "{text}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(42_u32), Answer::Integer(42));
+        assert_eq!(Answer::from(-3_i32), Answer::Integer(-3));
+        assert_eq!(
+            Answer::from(usize::MAX),
+            Answer::Integer(i128::from(u64::MAX))
+        );
+        assert_eq!(
+            Answer::from(String::from("#.\n.#\n")),
+            Answer::Text(String::from("#.\n.#\n"))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(1_234_usize).to_string(), "1234");
+        assert_eq!(Answer::from(-5_i32).to_string(), "-5");
+        assert_eq!(
+            Answer::from(String::from("#.\n.#\n")).to_string(),
+            "#.\n.#\n"
+        );
+    }
+}
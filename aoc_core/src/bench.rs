@@ -0,0 +1,127 @@
+use std::{
+    fmt::{Display, Formatter, Result as FormatResult},
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::Json;
+
+// However quick `f` is, measuring it more often than this tells nothing new
+const MAX_RUNS: u128 = 1_000;
+
+/// Calls `f` once to warm up, which also says how many more calls fit in
+/// `budget` (at least one), and times those.
+pub fn measure<T>(budget: Duration, mut f: impl FnMut() -> T) -> Statistics {
+    let mut time = || {
+        let start = Instant::now();
+        black_box(f());
+        start.elapsed()
+    };
+
+    let first = time();
+    let runs = (budget.as_nanos() / first.as_nanos().max(1)).clamp(1, MAX_RUNS);
+    let times: Vec<_> = (0..runs).map(|_| time()).collect();
+    Statistics::of(&times)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Statistics {
+    pub mean: Duration,
+    pub median: Duration,
+    pub std_dev: Duration,
+}
+
+impl Statistics {
+    /// # Panics
+    ///
+    /// Panics if there are no samples.
+    #[must_use]
+    pub fn of(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let count = u32::try_from(samples.len()).expect("at most u32::MAX samples");
+        let mean = samples.iter().sum::<Duration>() / count;
+        // Sample standard deviation, since the samples are just some of all possible runs
+        let variance = if count > 1 {
+            samples
+                .iter()
+                .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>()
+                / f64::from(count - 1)
+        } else {
+            0.0
+        };
+
+        Self {
+            mean,
+            median,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("mean_ns", Json::from(self.mean)),
+            ("median_ns", Json::from(self.median)),
+            ("std_dev_ns", Json::from(self.std_dev)),
+        ])
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(
+            formatter,
+            "mean {:.2?}, median {:.2?}, std dev {:.2?}",
+            self.mean, self.median, self.std_dev
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_statistics() {
+        let statistics = Statistics::of(&millis(&[4, 2, 9, 5]));
+        assert_eq!(statistics.mean, Duration::from_millis(5));
+        assert_eq!(statistics.median, Duration::from_micros(4_500));
+        // sqrt((1 + 9 + 16 + 0) / 3)
+        assert_eq!(statistics.std_dev.as_micros(), 2_943);
+
+        assert_eq!(
+            Statistics::of(&millis(&[7])),
+            Statistics {
+                mean: Duration::from_millis(7),
+                median: Duration::from_millis(7),
+                std_dev: Duration::ZERO,
+            }
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        measure(Duration::ZERO, || calls += 1);
+        assert_eq!(calls, 2);
+
+        let mut calls = 0;
+        measure(Duration::MAX, || calls += 1);
+        assert_eq!(calls, 1 + MAX_RUNS);
+    }
+}
@@ -0,0 +1,155 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::Rng;
+
+// Tokens that tend to trip up hand-written parsers
+const INTERESTING: [&str; 20] = [
+    "",
+    "0",
+    "9",
+    "-1",
+    "10",
+    "255",
+    "65536",
+    "18446744073709551616",
+    "999999999999999999999999999999",
+    " ",
+    "\n",
+    "\r\n",
+    "\n\n",
+    ",",
+    "->",
+    "=",
+    "x",
+    "é",
+    "\u{0}",
+    "\u{1f980}",
+];
+
+/// Randomly changes `input` a little: deletes, duplicates, swaps or replaces
+/// parts of it, inserts tokens that often trip up parsers, or cuts it short.
+///
+/// The result is always valid UTF-8, but usually not a valid puzzle input.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut characters: Vec<char> = input.chars().collect();
+    for _ in 0..rng.range(1..=4) {
+        let length = characters.len();
+        let start = rng.range(0..=length);
+        let end = rng.range(start..=length.min(start + 8));
+        match rng.below(6) {
+            0 => {
+                characters.drain(start..end);
+            }
+            1 => {
+                let copy = characters[start..end].to_vec();
+                characters.splice(end..end, copy);
+            }
+            2 if end < length => characters.swap(start, end),
+            3 if length > 0 => {
+                let replacement = *rng.choose(&characters);
+                characters[start.min(length - 1)] = replacement;
+            }
+            4 => characters.truncate(start),
+            _ => {
+                let token = rng.choose(&INTERESTING);
+                characters.splice(start..start, token.chars());
+            }
+        }
+    }
+    characters.into_iter().collect()
+}
+
+/// Calls `run` on `iterations` mutations of random `inputs` (which should be
+/// valid), and returns the smallest input found that makes it panic.
+///
+/// `run` (like parsing and solving a puzzle) is expected to return an error
+/// for the invalid inputs, not panic, so `None` means no problems were found.
+///
+/// # Panics
+///
+/// Panics if there are no `inputs`.
+pub fn find_panic<T>(
+    rng: &mut Rng,
+    inputs: &[String],
+    iterations: usize,
+    run: impl Fn(&str) -> T,
+) -> Option<String> {
+    let panics = |input: &str| panic::catch_unwind(AssertUnwindSafe(|| run(input))).is_err();
+
+    // Mutations pile up, so the inputs drift further from valid ones over time
+    let mut input = String::new();
+    for iteration in 0..iterations {
+        if iteration % 16 == 0 {
+            input.clone_from(rng.choose(inputs));
+        }
+        input = mutate(rng, &input);
+        if panics(&input) {
+            return Some(quietly(|| shrink(&input, panics)));
+        }
+    }
+    None
+}
+
+// Removes ever smaller parts of `input` for as long as it still fails
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut characters: Vec<char> = input.chars().collect();
+    let mut length = characters.len() / 2;
+    while length > 0 {
+        let mut start = 0;
+        while start + length <= characters.len() {
+            let mut candidate = characters.clone();
+            candidate.drain(start..start + length);
+            if fails(&candidate.iter().collect::<String>()) {
+                characters = candidate;
+            } else {
+                start += length;
+            }
+        }
+        length /= 2;
+    }
+    characters.into_iter().collect()
+}
+
+// Runs `f` without printing the message of every panic it causes
+fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(hook);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutate() {
+        let mut rng = Rng::new(3);
+        let mutations: Vec<_> = (0..100).map(|_| mutate(&mut rng, "forward 5\n")).collect();
+        assert!(mutations.iter().any(|mutation| mutation != "forward 5\n"));
+        assert_eq!(
+            mutate(&mut Rng::new(9), "up 3"),
+            mutate(&mut Rng::new(9), "up 3")
+        );
+        mutate(&mut rng, "");
+    }
+
+    #[test]
+    fn test_find_panic() {
+        let inputs = ["1,2,3".to_owned()];
+        let parse = |input: &str| {
+            input
+                .split(',')
+                .map(str::parse::<u8>)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        assert_eq!(find_panic(&mut Rng::new(0), &inputs, 1_000, parse), None);
+
+        let panic_on_x = |input: &str| assert!(!input.contains('x'));
+        assert_eq!(
+            find_panic(&mut Rng::new(0), &inputs, 1_000, panic_on_x),
+            Some("x".to_owned())
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    fmt::{Display, Formatter, Result as FormatResult, Write},
+    time::Duration,
+};
+
+use crate::Answer;
+
+/// Just enough JSON to write out results, without pulling in a dependency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Json {
+    Null,
+    Integer(i128),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl From<usize> for Json {
+    fn from(integer: usize) -> Self {
+        Self::Integer(i128::try_from(integer).expect("usize is at most 64 bits wide"))
+    }
+}
+
+// In nanoseconds, which is what the keys of durations end in
+impl From<Duration> for Json {
+    fn from(duration: Duration) -> Self {
+        Self::Integer(i128::try_from(duration.as_nanos()).unwrap_or(i128::MAX))
+    }
+}
+
+impl From<&str> for Json {
+    fn from(string: &str) -> Self {
+        Self::String(string.to_owned())
+    }
+}
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Integer(integer) => Self::Integer(*integer),
+            Answer::Text(text) => Self::from(text.as_str()),
+        }
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Null => formatter.write_str("null"),
+            Self::Integer(integer) => write!(formatter, "{integer}"),
+            Self::String(string) => write_string(formatter, string),
+            Self::Array(values) => {
+                formatter.write_char('[')?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        formatter.write_char(',')?;
+                    }
+                    write!(formatter, "{value}")?;
+                }
+                formatter.write_char(']')
+            }
+            Self::Object(fields) => {
+                formatter.write_char('{')?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        formatter.write_char(',')?;
+                    }
+                    write_string(formatter, key)?;
+                    write!(formatter, ":{value}")?;
+                }
+                formatter.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(formatter: &mut Formatter, string: &str) -> FormatResult {
+    formatter.write_char('"')?;
+    for character in string.chars() {
+        match character {
+            '"' => formatter.write_str("\\\"")?,
+            '\\' => formatter.write_str("\\\\")?,
+            '\n' => formatter.write_str("\\n")?,
+            '\r' => formatter.write_str("\\r")?,
+            '\t' => formatter.write_str("\\t")?,
+            control if control < ' ' => write!(formatter, "\\u{:04x}", u32::from(control))?,
+            _ => formatter.write_char(character)?,
+        }
+    }
+    formatter.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let json = Json::Object(vec![
+            ("day", Json::from(13)),
+            (
+                "answer",
+                Json::from(&Answer::Text("#.\n\"x\"\\".to_owned())),
+            ),
+            (
+                "errors",
+                Json::Array(vec![Json::from(2), Json::from("\u{1}")]),
+            ),
+            ("time_ns", Json::from(Duration::from_micros(3))),
+            ("error", Json::Null),
+        ]);
+        assert_eq!(
+            json.to_string(),
+            r##"{"day":13,"answer":"#.\n\"x\"\\","errors":[2,"\u0001"],"time_ns":3000,"error":null}"##
+        );
+    }
+}
@@ -0,0 +1,17 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+mod answer;
+mod bench;
+mod fuzz;
+mod json;
+mod parse;
+mod rng;
+mod solution;
+
+pub use answer::Answer;
+pub use bench::{measure, Statistics};
+pub use fuzz::{find_panic, mutate};
+pub use json::Json;
+pub use parse::{parse_lines, parse_token, ParseError};
+pub use rng::Rng;
+pub use solution::{Part, Solution, SolveError};
@@ -0,0 +1,210 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+    str::FromStr,
+};
+
+// Long tokens (like a whole line) are cut off, to keep error messages readable
+const MAX_TOKEN_LENGTH: usize = 32;
+
+/// A puzzle input that could not be parsed, and where.
+///
+/// Lines and columns are counted from 1, like editors do, and columns count
+/// characters rather than bytes.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    found: String,
+    expected: String,
+}
+
+impl ParseError {
+    #[must_use]
+    pub fn new(line: usize, column: usize, found: &str, expected: impl Into<String>) -> Self {
+        let found = match found.char_indices().nth(MAX_TOKEN_LENGTH) {
+            Some((end, _)) => format!("{}...", &found[..end]),
+            None => found.to_owned(),
+        };
+        Self {
+            line,
+            column,
+            found,
+            expected: expected.into(),
+        }
+    }
+
+    /// An error at `token`, which has to be a slice of `input`, so its
+    /// position can be worked out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `token` is not a slice of `input`.
+    #[must_use]
+    pub fn at(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(input, token);
+        Self::new(line, column, token, expected)
+    }
+
+    /// Moves an error found while parsing `part` of `input` to its position
+    /// in the whole of `input`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part` is not a slice of `input`.
+    #[must_use]
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let (line, column) = position(input, part);
+        Self {
+            line: line + self.line - 1,
+            column: if self.line == 1 {
+                column + self.column - 1
+            } else {
+                self.column
+            },
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    #[must_use]
+    pub fn found(&self) -> &str {
+        &self.found
+    }
+
+    #[must_use]
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(
+            formatter,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(formatter, "nothing")
+        } else {
+            write!(formatter, "`{}`", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token` (a slice of `input`) with [`str::parse`].
+///
+/// # Errors
+///
+/// Returns an error pointing at `token` if it doesn't parse.
+pub fn parse_token<T: FromStr>(input: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, expected))
+}
+
+/// Parses every line of `input`, with errors pointing into `input` rather than
+/// into the line.
+///
+/// # Errors
+///
+/// Returns the error of the first line that doesn't parse.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|error| error.within(input, line)))
+        .collect()
+}
+
+fn position(input: &str, token: &str) -> (usize, usize) {
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(input.as_ptr() as usize)
+        .filter(|&offset| offset + token.len() <= input.len())
+        .expect("token is a slice of input");
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "12,34\n56,7x\n";
+
+    #[test]
+    fn test_at() {
+        let error = ParseError::at(INPUT, &INPUT[9..11], "a number");
+        assert_eq!(
+            (
+                error.line(),
+                error.column(),
+                error.found(),
+                error.expected()
+            ),
+            (2, 4, "7x", "a number")
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected a number, found `7x`"
+        );
+        assert_eq!(
+            ParseError::at(INPUT, &INPUT[12..], "a line").to_string(),
+            "line 3, column 1: expected a line, found nothing"
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let error = ParseError::at(line, &line[3..], "a number").within(INPUT, line);
+        assert_eq!((error.line(), error.column()), (2, 4));
+
+        let error = ParseError::new(2, 3, "x", "a number").within(INPUT, &INPUT[3..]);
+        assert_eq!((error.line(), error.column()), (2, 3));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parse_line = |line: &str| -> Result<Vec<u8>, _> {
+            line.split(',')
+                .map(|number| parse_token(line, number, "a number"))
+                .collect()
+        };
+        assert_eq!(
+            parse_lines("1,2\n3,4\n", parse_line),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+        assert_eq!(
+            parse_lines(INPUT, parse_line),
+            Err(ParseError::new(2, 4, "7x", "a number"))
+        );
+    }
+
+    #[test]
+    fn test_long_token() {
+        let line = "x".repeat(40);
+        assert_eq!(
+            ParseError::at(&line, &line, "a number").found(),
+            format!("{}...", "x".repeat(32))
+        );
+    }
+}
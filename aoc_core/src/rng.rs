@@ -0,0 +1,110 @@
+use std::ops::RangeInclusive;
+
+/// A small pseudorandom number generator (`SplitMix64`), for generating puzzle
+/// inputs.
+///
+/// It's seeded explicitly, so the same seed gives the same numbers on every
+/// run and every machine. It's not meant for anything that needs to be
+/// unpredictable.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 up to, but not including, `bound`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is 0.
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "bound must be positive");
+        // Lemire's multiply-shift, which is biased by at most bound / 2^64
+        let bound = bound as u128;
+        let number = (u128::from(self.next_u64()) * bound) >> 64;
+        usize::try_from(number).expect("number is below bound")
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "range must not be empty");
+        match (end - start).checked_add(1) {
+            Some(length) => start + self.below(length),
+            None => usize::try_from(self.next_u64()).unwrap_or(usize::MAX),
+        }
+    }
+
+    /// `true` with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Fisher-Yates
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        // The first output of SplitMix64 seeded with 0
+        assert_eq!(numbers(0)[0], 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(42);
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            let number = rng.range(3..=6);
+            assert!((3..=6).contains(&number));
+            seen[number - 3] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(0..=usize::MAX);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<_> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{Answer, ParseError};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+/// A solver for one day of the puzzle.
+///
+/// Parsing is a separate step, so it can be timed (and fail) on its own, and
+/// so both parts can be solved from the same parsed input.
+pub trait Solution {
+    /// The parsed puzzle input, which may borrow from the raw input.
+    type Input<'a>;
+    type Error: Error;
+
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a valid puzzle input for this day.
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the parsed input has no answer for part 1.
+    fn part_1(input: &Self::Input<'_>) -> Result<Answer, Self::Error>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the parsed input has no answer for part 2.
+    fn part_2(input: &Self::Input<'_>) -> Result<Answer, Self::Error>;
+
+    /// Solves one part of the puzzle, straight from the raw input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either parsing or solving fails.
+    fn solve(input: &str, part: Part) -> Result<Answer, Self::Error> {
+        let input = Self::parse(input)?;
+        match part {
+            Part::One => Self::part_1(&input),
+            Part::Two => Self::part_2(&input),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<usize> for Part {
+    type Error = usize;
+
+    fn try_from(part: usize) -> Result<Self, Self::Error> {
+        match part {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            _ => Err(part),
+        }
+    }
+}
+
+impl From<Part> for usize {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::One => write!(formatter, "1"),
+            Self::Two => write!(formatter, "2"),
+        }
+    }
+}
+
+/// A day's input that either didn't parse, or parsed but has no answer, for
+/// a reason `E` that only that day knows about.
+#[derive(Debug)]
+pub enum SolveError<E> {
+    Parse(ParseError),
+    Solve(E),
+}
+
+impl<E> From<ParseError> for SolveError<E> {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl<E: Display> Display for SolveError<E> {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Parse(error) => write!(formatter, "{error}"),
+            Self::Solve(error) => write!(formatter, "{error}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for SolveError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(error) => Some(error),
+            Self::Solve(error) => Some(error),
+        }
+    }
+}
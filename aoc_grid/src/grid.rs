@@ -0,0 +1,259 @@
+use std::{
+    fmt::{Display, Formatter, Result as FormatResult},
+    ops::{Index, IndexMut},
+};
+
+/// A `(row, column)` pair, with `(0, 0)` in the top left corner.
+pub type Coordinate = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid, stored row by row in a single `Vec`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    num_rows: usize,
+    num_columns: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`.
+    pub fn new(num_rows: usize, num_columns: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            num_rows,
+            num_columns,
+            cells: vec![value; num_rows * num_columns],
+        }
+    }
+
+    /// A grid with every cell set to the result of calling `cell` with its coordinate.
+    pub fn from_fn(num_rows: usize, num_columns: usize, cell: impl FnMut(Coordinate) -> T) -> Self {
+        Self {
+            num_rows,
+            num_columns,
+            cells: (0..num_rows)
+                .flat_map(|row| (0..num_columns).map(move |column| (row, column)))
+                .map(cell)
+                .collect(),
+        }
+    }
+
+    /// A grid from cells listed row by row, or `None` if there are not
+    /// exactly `num_rows * num_columns` of them.
+    #[must_use]
+    pub fn from_cells(num_rows: usize, num_columns: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == num_rows * num_columns).then_some(Self {
+            num_rows,
+            num_columns,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    #[must_use]
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, (row, column): Coordinate) -> bool {
+        row < self.num_rows && column < self.num_columns
+    }
+
+    #[must_use]
+    pub fn get(&self, coordinate: Coordinate) -> Option<&T> {
+        self.index_of(coordinate).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, coordinate: Coordinate) -> Option<&mut T> {
+        self.index_of(coordinate)
+            .map(|index| &mut self.cells[index])
+    }
+
+    /// Every coordinate in the grid, row by row.
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> {
+        let num_columns = self.num_columns;
+        (0..self.num_rows).flat_map(move |row| (0..num_columns).map(move |column| (row, column)))
+    }
+
+    /// Every cell in the grid, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // max(1) because chunks panics on 0, which only happens when there are no cells anyway
+        self.cells.chunks(self.num_columns.max(1))
+    }
+
+    /// The coordinates directly above, left, right and below `coordinate`,
+    /// leaving out those outside the grid.
+    ///
+    /// The iterator doesn't borrow the grid, so the grid can be changed while
+    /// visiting the neighbours.
+    pub fn neighbours_4(&self, coordinate: Coordinate) -> impl Iterator<Item = Coordinate> {
+        self.neighbours(coordinate, &ORTHOGONAL)
+    }
+
+    /// Like [`Grid::neighbours_4`], but including the diagonal neighbours.
+    pub fn neighbours_8(&self, coordinate: Coordinate) -> impl Iterator<Item = Coordinate> {
+        self.neighbours(coordinate, &ALL_DIRECTIONS)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            num_rows: self.num_rows,
+            num_columns: self.num_columns,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn neighbours(
+        &self,
+        (row, column): Coordinate,
+        deltas: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Coordinate> {
+        let (num_rows, num_columns) = (self.num_rows, self.num_columns);
+        deltas.iter().filter_map(move |&(delta_row, delta_column)| {
+            let row = row.checked_add_signed(delta_row)?;
+            let column = column.checked_add_signed(delta_column)?;
+            (row < num_rows && column < num_columns).then_some((row, column))
+        })
+    }
+
+    fn index_of(&self, coordinate @ (row, column): Coordinate) -> Option<usize> {
+        self.contains(coordinate)
+            .then_some(row * self.num_columns + column)
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coordinate: Coordinate) -> &Self::Output {
+        match self.get(coordinate) {
+            Some(cell) => cell,
+            None => panic!(
+                "{coordinate:?} is outside of the {}x{} grid",
+                self.num_rows, self.num_columns
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, coordinate: Coordinate) -> &mut Self::Output {
+        let (num_rows, num_columns) = (self.num_rows, self.num_columns);
+        match self.get_mut(coordinate) {
+            Some(cell) => cell,
+            None => panic!("{coordinate:?} is outside of the {num_rows}x{num_columns} grid"),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        for row in self.rows() {
+            for cell in row {
+                write!(formatter, "{cell}")?;
+            }
+            writeln!(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_fn(3, 4, |(row, column)| {
+            u8::try_from(row * 4 + column).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = grid();
+        assert_eq!(grid[(1, 2)], 6);
+        assert_eq!(grid.get((2, 3)), Some(&11));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 4)), None);
+
+        grid[(2, 3)] = 42;
+        assert_eq!(grid.get((2, 3)), Some(&42));
+        assert!(grid.get_mut((3, 3)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 4) is outside of the 3x4 grid")]
+    fn test_index_out_of_bounds() {
+        let _ = grid()[(0, 4)];
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours_4((1, 1)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 2), (2, 1)]
+        );
+        assert_eq!(
+            grid.neighbours_8((2, 3)).collect::<Vec<_>>(),
+            [(1, 2), (1, 3), (2, 2)]
+        );
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_from_cells() {
+        assert_eq!(
+            Grid::from_cells(2, 2, vec![1, 2, 3, 4]),
+            Some(Grid::from_fn(2, 2, |(row, column)| row * 2 + column + 1))
+        );
+        assert_eq!(Grid::from_cells(2, 2, vec![1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::new(2, 3, 7).map(|cell| cell - 6);
+        assert_eq!(grid.to_string(), "111\n111\n");
+        assert_eq!(Grid::<u8>::new(0, 0, 0).to_string(), "");
+    }
+}
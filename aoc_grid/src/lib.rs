@@ -0,0 +1,6 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+mod grid;
+mod parse;
+
+pub use grid::{Coordinate, Grid};
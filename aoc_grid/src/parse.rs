@@ -0,0 +1,98 @@
+use crate::Grid;
+use aoc_core::ParseError;
+use std::str::FromStr;
+
+/// Parses a grid of single digits, one row per line, like `"123\n456\n"`.
+impl FromStr for Grid<u8> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut num_columns = None;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for line in input.lines() {
+            let expected = *num_columns.get_or_insert(line.chars().count());
+            if line.chars().count() != expected {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("a row of {expected} digits"),
+                ));
+            }
+            for (index, character) in line.char_indices() {
+                let digit = character.to_digit(10).ok_or_else(|| {
+                    let token = &line[index..index + character.len_utf8()];
+                    ParseError::at(input, token, "a digit")
+                })?;
+                cells.push(u8::try_from(digit).expect("a digit fits in a u8"));
+            }
+        }
+
+        match num_columns {
+            None | Some(0) => Err(ParseError::at(input, &input[..0], "a row of digits")),
+            Some(num_columns) => {
+                Ok(
+                    Self::from_cells(cells.len() / num_columns, num_columns, cells)
+                        .expect("every row has num_columns cells"),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::{find_panic, Rng};
+
+    fn parse_error(input: &str) -> String {
+        input.parse::<Grid<u8>>().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<u8> = "123\n456\n".parse().unwrap();
+        assert_eq!((grid.num_rows(), grid.num_columns()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!("12\r\n34".parse::<Grid<u8>>().unwrap()[(1, 1)], 4);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for empty in ["", "\n"] {
+            assert_eq!(
+                parse_error(empty),
+                "line 1, column 1: expected a row of digits, found nothing"
+            );
+        }
+        assert_eq!(
+            parse_error("123\n4x6\n"),
+            "line 2, column 2: expected a digit, found `x`"
+        );
+        assert_eq!(
+            parse_error("123\n45\n"),
+            "line 2, column 1: expected a row of 3 digits, found `45`"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = Rng::new(0);
+        for _ in 0..100 {
+            let (num_rows, num_columns) = (rng.range(1..=8), rng.range(1..=8));
+            let cells = (0..num_rows * num_columns)
+                .map(|_| u8::try_from(rng.below(10)).unwrap())
+                .collect();
+            let grid = Grid::from_cells(num_rows, num_columns, cells).unwrap();
+            assert_eq!(grid.to_string().parse::<Grid<u8>>(), Ok(grid));
+        }
+    }
+
+    #[test]
+    fn test_never_panics() {
+        let inputs = ["123\n456\n".to_owned(), "9".to_owned()];
+        let panic = find_panic(&mut Rng::new(0), &inputs, 2_000, str::parse::<Grid<u8>>);
+        assert_eq!(panic, None);
+    }
+}
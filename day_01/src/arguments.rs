@@ -0,0 +1,136 @@
+use std::{
+    fmt::{Display, Formatter, Result as FormatResult},
+    path::PathBuf,
+};
+
+pub const USAGE: &str = "Usage:
+    day_01 [--input <PATH>] [--windows <SIZE>,...] [--json]
+
+Reads depth measurements, one per line, from the input (stdin when no path or
+`-` is given), and counts how often the sums of sliding windows of each size
+increase. The window sizes are 1 and 3 by default, like in the puzzle.
+`--json` prints one JSON object instead of text.";
+
+// Every window keeps its depths in memory
+pub const MAX_WINDOW_SIZE: usize = 1_000_000;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Arguments {
+    /// `None` for stdin
+    pub input: Option<PathBuf>,
+    pub window_sizes: Vec<usize>,
+    pub json: bool,
+}
+
+impl Arguments {
+    pub fn parse(mut arguments: impl Iterator<Item = String>) -> Result<Self, ArgumentError> {
+        let mut input = None;
+        let mut window_sizes = vec![1, 3];
+        let mut json = false;
+
+        while let Some(flag) = arguments.next() {
+            let mut value = || {
+                arguments
+                    .next()
+                    .ok_or(ArgumentError::MissingValue(flag.clone()))
+            };
+            match flag.as_str() {
+                "--input" => {
+                    let path = value()?;
+                    input = (path != "-").then(|| PathBuf::from(path));
+                }
+                "--windows" => {
+                    window_sizes = value()?
+                        .split(',')
+                        .map(parse_window_size)
+                        .collect::<Result<_, _>>()?;
+                }
+                "--json" => json = true,
+                _ => return Err(ArgumentError::UnknownFlag(flag)),
+            }
+        }
+
+        Ok(Self {
+            input,
+            window_sizes,
+            json,
+        })
+    }
+}
+
+fn parse_window_size(size: &str) -> Result<usize, ArgumentError> {
+    size.parse()
+        .ok()
+        .filter(|size| (1..=MAX_WINDOW_SIZE).contains(size))
+        .ok_or_else(|| ArgumentError::InvalidWindowSize(size.to_owned()))
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ArgumentError {
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidWindowSize(String),
+}
+
+impl Display for ArgumentError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::UnknownFlag(flag) => write!(formatter, "unknown flag `{flag}`"),
+            Self::MissingValue(flag) => write!(formatter, "flag `{flag}` requires a value"),
+            Self::InvalidWindowSize(size) => write!(
+                formatter,
+                "window sizes must be from 1 to {MAX_WINDOW_SIZE}, got `{size}`"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(arguments: &str) -> Result<Arguments, ArgumentError> {
+        Arguments::parse(arguments.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(""),
+            Ok(Arguments {
+                input: None,
+                window_sizes: vec![1, 3],
+                json: false
+            })
+        );
+        assert_eq!(
+            parse("--json --windows 2,10,1 --input sonar.log"),
+            Ok(Arguments {
+                input: Some(PathBuf::from("sonar.log")),
+                window_sizes: vec![2, 10, 1],
+                json: true
+            })
+        );
+        assert_eq!(parse("--input -").unwrap().input, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("--windows 1,,3"),
+            Err(ArgumentError::InvalidWindowSize(String::new()))
+        );
+        assert_eq!(
+            parse("--windows 0").unwrap_err().to_string(),
+            "window sizes must be from 1 to 1000000, got `0`"
+        );
+        assert_eq!(
+            parse("--windows"),
+            Err(ArgumentError::MissingValue("--windows".to_owned()))
+        );
+        assert_eq!(
+            parse("--part 1"),
+            Err(ArgumentError::UnknownFlag("--part".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use aoc_core::Rng;
+use std::fmt::Write;
+
+/// A sonar sweep of `size` depth measurements, mostly going down like the
+/// sea floor does.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=200);
+    let mut input = String::with_capacity(6 * size);
+    for _ in 0..size {
+        writeln!(input, "{depth}").expect("writing to a String can't fail");
+        depth = (depth + rng.range(0..=30)).saturating_sub(10);
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(&mut Rng::new(1), 100).lines().count(), 100);
+    }
+}
@@ -0,0 +1,190 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+mod generate;
+mod profile;
+mod stream;
+
+use aoc_core::{parse_lines, parse_token, Answer, ParseError, Solution};
+pub use generate::generate;
+pub use profile::{Change, DepthProfile, Run};
+pub use stream::{count_increases, count_increases_in, Depths, ReadError, SlidingWindow};
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<i32>;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part_1(depth_measurements: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(count_sliding_sum_increases(depth_measurements, 1).into())
+    }
+
+    fn part_2(depth_measurements: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(count_sliding_sum_increases(depth_measurements, 3).into())
+    }
+}
+
+/// Depths can be zero or negative, for readings at or above sea level.
+///
+/// # Errors
+///
+/// Returns an error if a line is not a depth measurement.
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_lines(input, |line| parse_token(line, line, "a depth measurement"))
+}
+
+/// Like [`parse_input`], but also checks there are exactly `num_depths`
+/// depth measurements, to catch cut off or concatenated sonar logs.
+///
+/// # Errors
+///
+/// Returns an error if a line is not a depth measurement, or there are too
+/// many or too few of them.
+pub fn parse_exactly(input: &str, num_depths: usize) -> Result<Vec<i32>, DepthsError> {
+    let depths = parse_input(input).map_err(DepthsError::InvalidDepth)?;
+    if depths.len() == num_depths {
+        Ok(depths)
+    } else {
+        Err(DepthsError::WrongLength {
+            expected: num_depths,
+            actual: depths.len(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DepthsError {
+    InvalidDepth(ParseError),
+    WrongLength { expected: usize, actual: usize },
+}
+
+impl Display for DepthsError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::InvalidDepth(error) => write!(formatter, "{error}"),
+            Self::WrongLength { expected, actual } => write!(
+                formatter,
+                "expected {expected} depth measurements, but there are {actual}"
+            ),
+        }
+    }
+}
+
+impl Error for DepthsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidDepth(error) => Some(error),
+            Self::WrongLength { .. } => None,
+        }
+    }
+}
+
+#[must_use]
+pub fn count_sliding_sum_increases(numbers: &[i32], size: usize) -> usize {
+    // Little trick: when comparing two sliding sums, the last numbers of the
+    // first sum are the same as the first numbers of the second sum, so we
+    // only need to compare the first number of the first sum with the last
+    // number of the second sum.
+    numbers
+        .windows(size + 1)
+        .filter(|window| window[size] > window[0])
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Rng;
+
+    #[test]
+    fn test_part_1() {
+        // Arrange
+        let depth_measurements = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+        // Act
+        let num_increases = count_sliding_sum_increases(&depth_measurements, 1);
+
+        // Assert
+        assert_eq!(num_increases, 7);
+    }
+
+    #[test]
+    fn test_part_2() {
+        // Arrange
+        let depth_measurements = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+        // Act
+        let num_increases = count_sliding_sum_increases(&depth_measurements, 3);
+
+        // Assert
+        assert_eq!(num_increases, 5);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_input(
+                "199
+200
+2o8
+"
+            )
+            .unwrap_err()
+            .to_string(),
+            "line 3, column 1: expected a depth measurement, found `2o8`"
+        );
+    }
+
+    #[test]
+    fn test_against_naive() {
+        for seed in 0..10 {
+            let depths = parse_input(&generate(&mut Rng::new(seed), 200)).unwrap();
+            for size in 1..=4 {
+                let sums: Vec<i32> = depths
+                    .windows(size)
+                    .map(|window| window.iter().sum())
+                    .collect();
+                let naive = sums.windows(2).filter(|pair| pair[1] > pair[0]).count();
+                assert_eq!(count_sliding_sum_increases(&depths, size), naive);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_depths() {
+        let depths = parse_input("3\n0\n-2\n-1\n5\n").unwrap();
+        assert_eq!(depths, [3, 0, -2, -1, 5]);
+        assert_eq!(count_sliding_sum_increases(&depths, 1), 2);
+        // Sums 3, -2, -3, 4
+        assert_eq!(count_sliding_sum_increases(&depths, 2), 1);
+    }
+
+    #[test]
+    fn test_parse_exactly() {
+        assert_eq!(parse_exactly("1\n0\n2\n", 3), Ok(vec![1, 0, 2]));
+        assert_eq!(
+            parse_exactly("1\n0\n", 3).unwrap_err().to_string(),
+            "expected 3 depth measurements, but there are 2"
+        );
+        assert_eq!(
+            parse_exactly("1\n0\n2\n4\n", 3),
+            Err(DepthsError::WrongLength {
+                expected: 3,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            parse_exactly("1\n0\n2.5\n", 3).unwrap_err().to_string(),
+            "line 3, column 1: expected a depth measurement, found `2.5`"
+        );
+    }
+}
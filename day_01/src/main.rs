@@ -1,66 +1,74 @@
 #![deny(clippy::all, clippy::pedantic)]
 
-fn main() {
-    let depth_measurements = parse_input::<2000>(include_str!("depth-measurements.txt"));
+mod arguments;
 
-    println!(
-        "Part 1 - Number of increases in sea depth: {}",
-        count_sliding_sum_increases(&depth_measurements, 1)
-    );
+use aoc_core::Json;
+use arguments::{Arguments, USAGE};
+use day_01::count_increases_in;
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    process::ExitCode,
+};
 
-    println!(
-        "Part 2 - Number of sliding window increases in sea depth: {}",
-        count_sliding_sum_increases(&depth_measurements, 3)
-    );
-}
-
-// Parse to array rather than Vec to avoid heap allocation
-fn parse_input<const N: usize>(input: &str) -> [u32; N] {
-    let mut depth_measurements = [0; N];
-    for (index, line) in input.lines().enumerate() {
-        assert!(index < N);
-        depth_measurements[index] = line.parse().unwrap();
-    }
-    assert_ne!(depth_measurements[N - 1], 0);
-    depth_measurements
-}
-
-fn count_sliding_sum_increases<const N: usize>(numbers: &[u32; N], size: usize) -> usize {
-    // Little trick: when comparing two sliding sums, the last numbers of the
-    // first sum are the same as the first numbers of the second sum, so we
-    // only need to compare the first number of the first sum with the last
-    // number of the second sum.
-    numbers
-        .windows(size + 1)
-        .filter(|window| window[size] > window[0])
-        .count()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_1() {
-        // Arrange
-        let depth_measurements = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
-
-        // Act
-        let num_increases = count_sliding_sum_increases(&depth_measurements, 1);
-
-        // Assert
-        assert_eq!(num_increases, 7);
-    }
-
-    #[test]
-    fn test_part_2() {
-        // Arrange
-        let depth_measurements = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+fn main() -> ExitCode {
+    let Arguments {
+        input,
+        window_sizes,
+        json,
+    } = match Arguments::parse(env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("error: {error}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
 
-        // Act
-        let num_increases = count_sliding_sum_increases(&depth_measurements, 3);
+    let name = input
+        .as_ref()
+        .map_or_else(|| "stdin".to_owned(), |path| path.display().to_string());
+    // Streams the input, so long sonar logs don't have to fit in memory
+    let reader: Box<dyn BufRead> = match &input {
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(error) => {
+                eprintln!("error: could not read {name}: {error}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Box::new(io::stdin().lock()),
+    };
+    let increases = match count_increases_in(reader, &window_sizes) {
+        Ok(increases) => increases,
+        Err(error) => {
+            eprintln!("error: {name}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
 
-        // Assert
-        assert_eq!(num_increases, 5);
+    if json {
+        let windows = window_sizes
+            .iter()
+            .zip(&increases)
+            .map(|(&size, &increases)| {
+                Json::Object(vec![
+                    ("size", Json::from(size)),
+                    ("increases", Json::from(increases)),
+                ])
+            })
+            .collect();
+        println!(
+            "{}",
+            Json::Object(vec![
+                ("input", Json::from(name.as_str())),
+                ("windows", Json::Array(windows)),
+            ])
+        );
+    } else {
+        for (size, increases) in window_sizes.iter().zip(&increases) {
+            println!("Window size {size}: {increases} increases");
+        }
     }
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,236 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+/// How the sums of a sliding window of depths change from one window to the
+/// next, for spotting odd sensor readings as much as for the puzzle.
+///
+/// Window `i` is the window starting at depth `i`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DepthProfile {
+    pub window_size: usize,
+    pub num_windows: usize,
+    pub increases: usize,
+    pub decreases: usize,
+    pub plateaus: usize,
+    pub longest_increasing_run: Option<Run>,
+    pub longest_decreasing_run: Option<Run>,
+    pub largest_change: Option<Change>,
+    /// The windows with a larger sum than the window before
+    pub increase_indices: Vec<usize>,
+}
+
+/// Windows `start..start + length`, with sums that keep going up (or down).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Run {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// How much the sum changed from window `index - 1` to window `index`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Change {
+    pub index: usize,
+    pub change: i64,
+}
+
+impl DepthProfile {
+    /// Profiles `depths` in one pass, remembering only the last
+    /// `window_size` depths (and the increases).
+    ///
+    /// When runs or changes tie, the first one counts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is 0.
+    pub fn new(depths: impl IntoIterator<Item = i32>, window_size: usize) -> Self {
+        assert!(window_size > 0, "windows need at least one depth");
+        let mut profile = Self {
+            window_size,
+            ..Self::default()
+        };
+
+        let mut window = Vec::with_capacity(window_size);
+        let mut oldest = 0;
+        let mut sum = 0;
+        let mut previous_sum = None;
+        // Each run that's still going, if it's longer than one window
+        let (mut increasing, mut decreasing): (Option<Run>, Option<Run>) = (None, None);
+
+        for depth in depths {
+            let depth = i64::from(depth);
+            if window.len() < window_size {
+                window.push(depth);
+                sum += depth;
+                if window.len() < window_size {
+                    continue;
+                }
+            } else {
+                sum += depth - window[oldest];
+                window[oldest] = depth;
+                oldest = (oldest + 1) % window_size;
+            }
+
+            let index = profile.num_windows;
+            profile.num_windows += 1;
+            let Some(previous_sum) = previous_sum.replace(sum) else {
+                continue;
+            };
+
+            let change = sum - previous_sum;
+            match change.cmp(&0) {
+                Ordering::Greater => {
+                    profile.increases += 1;
+                    profile.increase_indices.push(index);
+                    extend(&mut increasing, index, &mut profile.longest_increasing_run);
+                    decreasing = None;
+                }
+                Ordering::Less => {
+                    profile.decreases += 1;
+                    extend(&mut decreasing, index, &mut profile.longest_decreasing_run);
+                    increasing = None;
+                }
+                Ordering::Equal => {
+                    profile.plateaus += 1;
+                    (increasing, decreasing) = (None, None);
+                }
+            }
+            if profile
+                .largest_change
+                .is_none_or(|largest| change.abs() > largest.change.abs())
+            {
+                profile.largest_change = Some(Change { index, change });
+            }
+        }
+
+        profile
+    }
+}
+
+// Adds window `index` to the run going on (or starts one with the window
+// before it), and keeps track of the longest run
+fn extend(run: &mut Option<Run>, index: usize, longest: &mut Option<Run>) {
+    let run = run.get_or_insert(Run {
+        start: index - 1,
+        length: 1,
+    });
+    run.length += 1;
+    if longest.is_none_or(|longest| run.length > longest.length) {
+        *longest = Some(*run);
+    }
+}
+
+impl Display for DepthProfile {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        writeln!(
+            formatter,
+            "Window size {}, {} windows",
+            self.window_size, self.num_windows
+        )?;
+        writeln!(
+            formatter,
+            "Increases: {}, decreases: {}, plateaus: {}",
+            self.increases, self.decreases, self.plateaus
+        )?;
+        for (name, run) in [
+            ("increasing", self.longest_increasing_run),
+            ("decreasing", self.longest_decreasing_run),
+        ] {
+            match run {
+                Some(Run { start, length }) => writeln!(
+                    formatter,
+                    "Longest {name} run: {length} windows from window {start}"
+                )?,
+                None => writeln!(formatter, "Longest {name} run: none")?,
+            }
+        }
+        match self.largest_change {
+            Some(Change { index, change }) => {
+                writeln!(formatter, "Largest change: {change:+} at window {index}")
+            }
+            None => writeln!(formatter, "Largest change: none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_sliding_sum_increases, generate, parse_input};
+    use aoc_core::Rng;
+
+    const DEPTHS: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_profile() {
+        assert_eq!(
+            DepthProfile::new(DEPTHS, 1),
+            DepthProfile {
+                window_size: 1,
+                num_windows: 10,
+                increases: 7,
+                decreases: 2,
+                plateaus: 0,
+                longest_increasing_run: Some(Run {
+                    start: 0,
+                    length: 4
+                }),
+                longest_decreasing_run: Some(Run {
+                    start: 3,
+                    length: 2
+                }),
+                largest_change: Some(Change {
+                    index: 6,
+                    change: 33
+                }),
+                increase_indices: vec![1, 2, 3, 5, 6, 7, 9],
+            }
+        );
+
+        // Sums 607, 618, 618, 617, 647, 716, 769, 792
+        let profile = DepthProfile::new(DEPTHS, 3);
+        assert_eq!(
+            (profile.increases, profile.decreases, profile.plateaus),
+            (5, 1, 1)
+        );
+        assert_eq!(profile.increase_indices, [1, 4, 5, 6, 7]);
+        assert_eq!(
+            profile.to_string(),
+            "Window size 3, 8 windows
+Increases: 5, decreases: 1, plateaus: 1
+Longest increasing run: 5 windows from window 3
+Longest decreasing run: 2 windows from window 2
+Largest change: +69 at window 5
+"
+        );
+    }
+
+    #[test]
+    fn test_few_depths() {
+        let profile = DepthProfile::new([5, 5], 3);
+        assert_eq!(profile.num_windows, 0);
+        assert_eq!(profile.largest_change, None);
+
+        let profile = DepthProfile::new([5, 5, 5, 1], 3);
+        assert_eq!((profile.num_windows, profile.decreases), (2, 1));
+        assert_eq!(profile.longest_increasing_run, None);
+        assert!(profile.to_string().contains("Longest increasing run: none"));
+    }
+
+    #[test]
+    fn test_against_count() {
+        let depths = parse_input(&generate(&mut Rng::new(8), 300)).unwrap();
+        for window_size in 1..=5 {
+            let profile = DepthProfile::new(depths.iter().copied(), window_size);
+            assert_eq!(
+                profile.increases,
+                count_sliding_sum_increases(&depths, window_size)
+            );
+            assert_eq!(
+                profile.increases + profile.decreases + profile.plateaus + 1,
+                profile.num_windows
+            );
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use aoc_core::ParseError;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+    io::{self, BufRead},
+};
+
+/// Counts how often the sum of a sliding window of depths increases, one depth
+/// at a time, remembering only the last `size` depths.
+#[derive(Clone, Debug)]
+pub struct SlidingWindow {
+    size: usize,
+    // The last `size` depths, with the oldest at `oldest` once it's full
+    depths: Vec<i32>,
+    oldest: usize,
+    increases: usize,
+}
+
+impl SlidingWindow {
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            depths: Vec::with_capacity(size),
+            oldest: 0,
+            increases: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: i32) {
+        if self.depths.len() < self.size {
+            self.depths.push(depth);
+            return;
+        }
+        // Same trick as `count_sliding_sum_increases`: the next sum only
+        // differs from the last one by the depth leaving the window
+        if let Some(oldest) = self.depths.get_mut(self.oldest) {
+            if depth > *oldest {
+                self.increases += 1;
+            }
+            *oldest = depth;
+            self.oldest = (self.oldest + 1) % self.size;
+        }
+    }
+
+    #[must_use]
+    pub fn increases(&self) -> usize {
+        self.increases
+    }
+}
+
+/// Counts the sliding sum increases of `depths` for every window size in
+/// `sizes` in one pass, in the same order as `sizes`.
+pub fn count_increases(depths: impl IntoIterator<Item = i32>, sizes: &[usize]) -> Vec<usize> {
+    let mut windows: Vec<_> = sizes.iter().copied().map(SlidingWindow::new).collect();
+    for depth in depths {
+        for window in &mut windows {
+            window.push(depth);
+        }
+    }
+    windows.iter().map(SlidingWindow::increases).collect()
+}
+
+/// Like [`count_increases`], but reads one depth per line from `reader`, so
+/// the depths never all have to be in memory.
+///
+/// # Errors
+///
+/// Returns an error if reading fails, or a line is not a depth measurement.
+pub fn count_increases_in(reader: impl BufRead, sizes: &[usize]) -> Result<Vec<usize>, ReadError> {
+    let mut windows: Vec<_> = sizes.iter().copied().map(SlidingWindow::new).collect();
+    for depth in Depths::new(reader) {
+        let depth = depth?;
+        for window in &mut windows {
+            window.push(depth);
+        }
+    }
+    Ok(windows.iter().map(SlidingWindow::increases).collect())
+}
+
+/// The depths in a reader, one per line, parsed like [`crate::parse_input`]
+/// does.
+pub struct Depths<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> Depths<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Depths<R> {
+    type Item = Result<i32, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.line.clear();
+        match self.reader.read_line(&mut self.line) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.line_number += 1;
+                let line = self.line.strip_suffix('\n').unwrap_or(&self.line);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                Some(line.parse().map_err(|_| {
+                    ReadError::InvalidDepth(ParseError::new(
+                        self.line_number,
+                        1,
+                        line,
+                        "a depth measurement",
+                    ))
+                }))
+            }
+            Err(error) => Some(Err(ReadError::Io(error))),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    InvalidDepth(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Io(error) => write!(formatter, "could not read depths: {error}"),
+            Self::InvalidDepth(error) => write!(formatter, "{error}"),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::InvalidDepth(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_sliding_sum_increases, generate, parse_input};
+    use aoc_core::Rng;
+
+    #[test]
+    fn test_count_increases() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(count_increases(depths, &[1, 3, 0, 10]), [7, 5, 0, 0]);
+
+        let input = generate(&mut Rng::new(5), 500);
+        let depths = parse_input(&input).unwrap();
+        let sizes: Vec<_> = (0..=6).collect();
+        let expected: Vec<_> = sizes
+            .iter()
+            .map(|&size| count_sliding_sum_increases(&depths, size))
+            .collect();
+        assert_eq!(count_increases(depths.iter().copied(), &sizes), expected);
+        assert_eq!(
+            count_increases_in(input.as_bytes(), &sizes).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_count_increases_in() {
+        assert_eq!(
+            count_increases_in("1\r\n3\r\n2\r\n4".as_bytes(), &[1]).unwrap(),
+            [2]
+        );
+        let input = "199\n200\n2o8\n";
+        let error = count_increases_in(input.as_bytes(), &[1]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            parse_input(input).unwrap_err().to_string()
+        );
+        assert_eq!(
+            count_increases_in("1\n\n2\n".as_bytes(), &[1])
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: expected a depth measurement, found nothing"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+//! Compares the day 2 parsers on generated courses of different sizes.
+//!
+//! Run with `cargo bench -p day_02`.
+
+use aoc_core::{measure, ParseError, Rng};
+use day_02::{fast_parse, generate, parse_chunked, parse_input, Command};
+use std::{hint::black_box, time::Duration};
+
+const SIZES: [usize; 3] = [1_000, 100_000, 10_000_000];
+
+// Roughly how long to spend measuring each parser on each size
+const BUDGET: Duration = Duration::from_secs(2);
+
+type Parser = fn(&str) -> Result<Vec<Command>, ParseError>;
+
+const PARSERS: [(&str, Parser); 3] = [
+    ("fast_parse", fast_parse),
+    ("parse_chunked", parse_chunked),
+    ("parse_input", parse_input),
+];
+
+fn main() {
+    for size in SIZES {
+        let input = generate(&mut Rng::new(2), size);
+        println!("{size} commands ({} bytes):", input.len());
+        for (name, parse) in PARSERS {
+            let median = measure(BUDGET, || {
+                let commands = parse(black_box(&input));
+                assert!(commands.is_ok(), "generated inputs are valid");
+                commands
+            })
+            .median;
+            #[allow(clippy::cast_precision_loss)]
+            let throughput = input.len() as f64 / median.as_secs_f64() / 1e6;
+            println!("    {name:<13} {median:>12.2?} ({throughput:>7.1} MB/s)");
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{parse_input, Command};
+use aoc_core::ParseError;
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+// How many words to find the line breaks in before parsing the lines
+const BLOCK_WORDS: usize = 64;
+
+/// Parses the same commands as [`parse_input`], but 8 bytes at a time:
+/// first it finds the line breaks in a block of the input without branching,
+/// then it checks each keyword and reads each amount in one go.
+///
+/// That's only for lines like `forward 12`, `down 5` and `up 3` separated by
+/// `\n`, which is what real inputs look like. Anything else (including every
+/// error) goes through [`parse_input`], so the results are always the same.
+///
+/// # Errors
+///
+/// Returns an error pointing at the first command or amount that isn't one.
+pub fn parse_chunked(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_plain(input.as_bytes()).map_or_else(|| parse_input(input), Ok)
+}
+
+// `None` as soon as a line isn't a plain command
+fn parse_plain(bytes: &[u8]) -> Option<Vec<Command>> {
+    // The shortest command ("up 1\n") is 5 bytes, so this never reallocates,
+    // and a word has at most two line breaks
+    let mut commands = Vec::with_capacity(bytes.len() / 5 + 1);
+
+    let (words, rest) = bytes.as_chunks::<8>();
+    let mut words = words
+        .iter()
+        .map(|&word| u64::from_le_bytes(word))
+        .chain((!rest.is_empty()).then(|| load(rest)))
+        .enumerate()
+        .peekable();
+    // Both line breaks of a word are written down whether they're there or
+    // not, so one more fits, and then the end of the input
+    let mut ends = [0; 2 * BLOCK_WORDS + 2];
+    let mut start = 0;
+    loop {
+        let mut count = 0;
+        for (index, word) in words.by_ref().take(BLOCK_WORDS) {
+            let newlines = find_bytes(word, b'\n');
+            let found = newlines.count_ones() as usize;
+            if found > 2 {
+                return None;
+            }
+            ends[count] = 8 * index + newlines.trailing_zeros() as usize / 8;
+            let newlines = newlines & newlines.wrapping_sub(1);
+            ends[count + 1] = 8 * index + newlines.trailing_zeros() as usize / 8;
+            count += found;
+        }
+        let done = words.peek().is_none();
+        // The last line doesn't need a line break
+        if done && bytes.last().is_some_and(|&byte| byte != b'\n') {
+            ends[count] = bytes.len();
+            count += 1;
+        }
+
+        for &end in &ends[..count] {
+            commands.push(parse_line(&bytes[start..], end - start)?);
+            start = end + 1;
+        }
+        if done {
+            return Some(commands);
+        }
+    }
+}
+
+// The line is the first `length` of `bytes`, but the words read can go past
+// it, which saves copying short lines into a word of their own
+fn parse_line(bytes: &[u8], length: usize) -> Option<Command> {
+    // No branching on the keyword, since the commands come in no particular
+    // order
+    let keyword = KEYWORDS[usize::from(*bytes.first()?)];
+    // The whole keyword, with the space after it, in one comparison
+    if load(bytes) & mask(keyword.length) != keyword.word {
+        return None;
+    }
+    let rest = &bytes[keyword.length..];
+    let amount = match length.checked_sub(keyword.length)? {
+        0 => return None,
+        // Real inputs only have these
+        1 => match rest[0].wrapping_sub(b'0') {
+            digit @ 0..=9 => i32::from(digit),
+            _ => return None,
+        },
+        digits @ 2..=8 => parse_digits(load(rest) & mask(digits), digits)?,
+        // Too long for one word, but rare enough to read digit by digit
+        digits => parse_long_amount(rest.get(..digits)?)?,
+    };
+    Some(keyword.kind.with(amount))
+}
+
+// From 0 to `i32::MAX`, like `str::parse` but without a sign
+fn parse_long_amount(digits: &[u8]) -> Option<i32> {
+    digits.iter().try_fold(0_i32, |amount, &digit| {
+        let digit = i32::from(digit.wrapping_sub(b'0'));
+        if digit > 9 {
+            return None;
+        }
+        amount.checked_mul(10)?.checked_add(digit)
+    })
+}
+
+// The first `length` bytes of `word` as ASCII digits, all at once (the rest
+// of `word` has to be zeros)
+fn parse_digits(word: u64, length: usize) -> Option<i32> {
+    // Moves the digits to the end of the word, and pads them with leading
+    // zeros. The first digit is in the lowest byte, since it's little-endian.
+    let padding = 8 * (8 - length);
+    let zeros = (ONES * u64::from(b'0')) & !(u64::MAX << padding);
+    let word = (word << padding) | zeros;
+
+    // Only bytes from `0` to `9` stay below 0x80 both ways
+    let too_large = word.wrapping_add(ONES * (0x7f - u64::from(b'9')));
+    let too_small = word.wrapping_sub(ONES * u64::from(b'0'));
+    if (too_large | too_small) & HIGH_BITS != 0 {
+        return None;
+    }
+
+    // Combines pairs of digits, then pairs of those, and so on
+    let word = too_small;
+    let word = (word.wrapping_mul(10) + (word >> 8)) & 0x00ff_00ff_00ff_00ff;
+    let word = (word.wrapping_mul(100) + (word >> 16)) & 0x0000_ffff_0000_ffff;
+    let word = word.wrapping_mul(10_000) + (word >> 32);
+    i32::try_from(word & 0xffff_ffff).ok()
+}
+
+// A bit 0x80 in every byte of `word` that is `byte`
+fn find_bytes(word: u64, byte: u8) -> u64 {
+    let zeros = word ^ (ONES * u64::from(byte));
+    // Exact, unlike the usual `(x - ONES) & !x`, which can flag the byte
+    // after a match
+    !(((zeros & !HIGH_BITS) + !HIGH_BITS) | zeros | !HIGH_BITS)
+}
+
+// Up to the first 8 bytes, padded with zeros
+fn load(bytes: &[u8]) -> u64 {
+    if let Some(&word) = bytes.first_chunk() {
+        return u64::from_le_bytes(word);
+    }
+    let mut word = [0; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+// The lowest `length` bytes, from 1 to 8
+fn mask(length: usize) -> u64 {
+    u64::MAX >> (64 - 8 * length)
+}
+
+#[derive(Clone, Copy)]
+struct Keyword {
+    // With the space after it
+    word: u64,
+    length: usize,
+    kind: Kind,
+}
+
+impl Keyword {
+    const fn new(keyword: &[u8], kind: Kind) -> Self {
+        let mut word = 0;
+        let mut index = keyword.len();
+        while index > 0 {
+            index -= 1;
+            word = word << 8 | keyword[index] as u64;
+        }
+        Self {
+            word,
+            length: keyword.len(),
+            kind,
+        }
+    }
+}
+
+// The keyword a line has to start with, by its first byte
+const KEYWORDS: [Keyword; 256] = {
+    // Lines starting with any other byte never match
+    let mut keywords = [Keyword::new(b"\0", Kind::Up); 256];
+    keywords[0] = Keyword::new(b"\xff", Kind::Up);
+    keywords[b'f' as usize] = Keyword::new(b"forward ", Kind::Forward);
+    keywords[b'd' as usize] = Keyword::new(b"down ", Kind::Down);
+    keywords[b'u' as usize] = Keyword::new(b"up ", Kind::Up);
+    keywords
+};
+
+// Which command a keyword is for. A function would do, but calling it costs
+// more than parsing the rest of the line.
+#[derive(Clone, Copy)]
+enum Kind {
+    Forward,
+    Down,
+    Up,
+}
+
+impl Kind {
+    fn with(self, amount: i32) -> Command {
+        match self {
+            Self::Forward => Command::Forward(amount),
+            Self::Down => Command::Down(amount),
+            Self::Up => Command::Up(amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+    use aoc_core::{mutate, Rng};
+
+    #[test]
+    fn test_find_bytes() {
+        assert_eq!(find_bytes(load(b"up 3\nup "), b'\n'), 0x80 << 32);
+        assert_eq!(find_bytes(load(b"\n\n\x0b\x09"), b'\n'), 0x8080);
+        assert_eq!(find_bytes(load(b"forward "), b'\n'), 0);
+        assert_eq!(find_bytes(u64::MAX, b'\xff'), HIGH_BITS);
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let parse = |line: &str| parse_line(line.as_bytes(), line.len());
+        for amount in [0, 7, 10, 99, 1_234_567, 12_345_678, 123_456_789, i32::MAX] {
+            assert_eq!(
+                parse(&format!("down {amount}")),
+                Some(Command::Down(amount))
+            );
+        }
+        assert_eq!(parse("up 00000042"), Some(Command::Up(42)));
+        assert_eq!(parse("up 000000000042"), Some(Command::Up(42)));
+        // Only the line counts, not what comes after it
+        assert_eq!(
+            parse_line(b"forward 12\nup 3", 10),
+            Some(Command::Forward(12))
+        );
+        assert_eq!(parse_line(b"up\nup 3", 2), None);
+        assert_eq!(parse_line(b"up 3\n", 3), None);
+        for invalid in [
+            "",
+            "up ",
+            "up 2147483648",
+            "up 12345678901",
+            "up -1",
+            "up +1",
+            "up 1 ",
+            "up 1\r",
+            "up /",
+            "up :",
+            "up 1234567a",
+            "up 12345678a",
+            "upp 1",
+            "forwar 1",
+            "f",
+        ] {
+            assert_eq!(parse(invalid), None, "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_chunked() {
+        let input = "forward 5\ndown 12345678\nup 123456789\nforward 0\n";
+        assert_eq!(parse_plain(input.as_bytes()), parse_input(input).ok());
+        assert_eq!(parse_plain(b""), Some(vec![]));
+        // Valid, but not plain
+        for input in [
+            "forward 5\r\n",
+            "down 5\n\nup 3",
+            "set-aim -2",
+            "up  3",
+            "up 3 ",
+        ] {
+            assert_eq!(parse_plain(input.as_bytes()), None, "{input:?}");
+            assert_eq!(parse_chunked(input), parse_input(input));
+        }
+        assert_eq!(
+            parse_chunked("down 5\nforwarb 5").unwrap_err().to_string(),
+            parse_input("down 5\nforwarb 5").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_agrees_with_parse_input() {
+        let mut rng = Rng::new(19);
+        let inputs: Vec<_> = (0..20).map(|size| generate(&mut rng, size)).collect();
+        for input in &inputs {
+            assert!(parse_plain(input.as_bytes()).is_some());
+            assert_eq!(parse_chunked(input), parse_input(input));
+        }
+        for _ in 0..5_000 {
+            let valid = rng.choose(&inputs);
+            let input = mutate(&mut rng, valid);
+            assert_eq!(parse_chunked(&input), parse_input(&input), "{input:?}");
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use aoc_core::Rng;
+use std::fmt::Write;
+
+/// A course of `size` commands with single digit amounts.
+///
+/// The aim stays small, so the depth only grows about as fast as the
+/// horizontal position.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut aim = 0;
+    let mut input = String::with_capacity(10 * size);
+    for _ in 0..size {
+        let amount = rng.range(1..=9);
+        // Keeps the aim between 0 and 59
+        let command = match rng.below(3) {
+            0 => "forward",
+            1 if aim <= 50 => {
+                aim += amount;
+                "down"
+            }
+            _ if aim >= amount => {
+                aim -= amount;
+                "up"
+            }
+            _ => {
+                aim += amount;
+                "down"
+            }
+        };
+        writeln!(input, "{command} {amount}").expect("writing to a String can't fail");
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day02;
+    use aoc_core::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 1_000);
+        assert_eq!(Day02::parse(&input).unwrap().len(), 1_000);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::Command;
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FormatResult},
+};
+
+/// The integers a submarine can keep track of where it is in.
+///
+/// Every command amount fits in any of them, but how far the submarine can
+/// get before its position overflows depends on the type.
+pub trait Integer: Copy + Debug + Default + Display + Eq + From<i32> {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl Integer for $integer {
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$integer>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$integer>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$integer>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i32, i64, i128);
+
+/// The submarine went further than its integer type can count.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Overflow {
+    /// Following command `index` (counting from 0, like [`crate::Step`]).
+    Command { index: usize, command: Command },
+    /// Multiplying the final depth by the final horizontal position.
+    Result,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Command { index, command } => {
+                write!(formatter, "command {index} (`{command}`) overflows")
+            }
+            Self::Result => write!(
+                formatter,
+                "the depth times the horizontal position overflows"
+            ),
+        }
+    }
+}
+
+impl Error for Overflow {}
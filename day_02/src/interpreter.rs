@@ -0,0 +1,67 @@
+use crate::{Command, Integer, SubmarineState};
+
+/// The physics of a submarine: how a command changes its state, counting in
+/// `T`.
+///
+/// Any `Fn(SubmarineState<T>, Command) -> Option<SubmarineState<T>>` is an
+/// interpreter, so new physics don't need a type of their own.
+pub trait Interpreter<T = i64> {
+    /// Returns `None` if the new state doesn't fit in a `T`.
+    fn apply(&self, state: SubmarineState<T>, command: Command) -> Option<SubmarineState<T>>;
+}
+
+impl<T, F> Interpreter<T> for F
+where
+    F: Fn(SubmarineState<T>, Command) -> Option<SubmarineState<T>>,
+{
+    fn apply(&self, state: SubmarineState<T>, command: Command) -> Option<SubmarineState<T>> {
+        self(state, command)
+    }
+}
+
+/// The two ways the puzzle reads the commands.
+///
+/// Both move backward the way they move forward, but the other way, go
+/// straight up to the surface on `surface`, and set the aim on `set-aim`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interpretation {
+    /// `down` and `up` change the depth, like part 1 assumes.
+    Depth,
+    /// `down` and `up` change the aim, like the manual says in part 2.
+    Aim,
+}
+
+impl<T: Integer> Interpreter<T> for Interpretation {
+    fn apply(&self, state: SubmarineState<T>, command: Command) -> Option<SubmarineState<T>> {
+        match self {
+            Self::Depth => state.apply_command_incorrectly(command),
+            Self::Aim => state.apply_command(command),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aggregate_commands, parse_input};
+
+    #[test]
+    fn test_custom_interpreter() {
+        // A submarine with a broken depth gauge, that never leaves the surface
+        let stuck = |state: SubmarineState, command| {
+            Some(SubmarineState {
+                depth: 0,
+                ..Interpretation::Aim.apply(state, command)?
+            })
+        };
+        let commands = parse_input("down 5\nforward 3\nup 1\nforward 2\n").unwrap();
+        assert_eq!(
+            aggregate_commands(&commands, &stuck),
+            Ok(SubmarineState {
+                depth: 0,
+                horizontal: 5,
+                aim: 4
+            })
+        );
+    }
+}
@@ -1,171 +1,530 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(maybe_uninit_uninit_array, maybe_uninit_array_assume_init, test)]
 
-extern crate test;
+mod chunked;
+mod generate;
+mod integer;
+mod interpreter;
+mod trajectory;
 
-use std::mem::MaybeUninit;
+use aoc_core::{parse_lines, Answer, ParseError, Solution};
+pub use chunked::parse_chunked;
+pub use generate::generate;
+pub use integer::{Integer, Overflow};
+pub use interpreter::{Interpretation, Interpreter};
+pub use trajectory::{trajectory, write_csv, Step};
 
-const INPUT: &str = include_str!("commands.txt");
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
 
-fn part_1(commands: &[Command; 1_000]) -> i32 {
-    aggregate_commands(commands, SubmarineState::apply_command_incorrectly).result()
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Command>;
+    type Error = DiveError;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_chunked(input)?)
+    }
+
+    fn part_1(commands: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(commands)?.into())
+    }
+
+    fn part_2(commands: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(commands)?.into())
+    }
 }
 
-fn part_2(commands: &[Command; 1_000]) -> i32 {
-    aggregate_commands(commands, SubmarineState::apply_command).result()
+fn part_1(commands: &[Command]) -> Result<i64, Overflow> {
+    aggregate_commands(commands, &Interpretation::Depth)?.result()
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Command {
-    AdjustAim(i32),
-    MoveForward(i32),
+fn part_2(commands: &[Command]) -> Result<i64, Overflow> {
+    aggregate_commands(commands, &Interpretation::Aim)?.result()
 }
 
-#[derive(Clone, Copy, Default)]
-struct SubmarineState {
-    depth: i32,
-    horizontal: i32,
-    aim: i32,
+/// What an interpreter makes of these is up to it, but the names say what
+/// the built-in ones do.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Command {
+    Forward(i32),
+    Backward(i32),
+    Down(i32),
+    Up(i32),
+    /// Straight up to a depth of 0.
+    Surface,
+    SetAim(i32),
 }
 
-impl SubmarineState {
-    fn apply_command_incorrectly(self, command: Command) -> Self {
-        match command {
-            Command::AdjustAim(x) => Self {
-                depth: self.depth + x,
+// The way it's written in the input
+impl Display for Command {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Forward(x) => write!(formatter, "forward {x}"),
+            Self::Backward(x) => write!(formatter, "backward {x}"),
+            Self::Down(x) => write!(formatter, "down {x}"),
+            Self::Up(x) => write!(formatter, "up {x}"),
+            Self::Surface => write!(formatter, "surface"),
+            Self::SetAim(x) => write!(formatter, "set-aim {x}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SubmarineState<T = i64> {
+    pub depth: T,
+    pub horizontal: T,
+    pub aim: T,
+}
+
+// `None` when the new state doesn't fit in a `T`
+impl<T: Integer> SubmarineState<T> {
+    fn apply_command_incorrectly(self, command: Command) -> Option<Self> {
+        Some(match command {
+            Command::Forward(x) => Self {
+                horizontal: self.horizontal.checked_add(x.into())?,
                 ..self
             },
-            Command::MoveForward(x) => Self {
-                horizontal: self.horizontal + x,
+            Command::Backward(x) => Self {
+                horizontal: self.horizontal.checked_sub(x.into())?,
                 ..self
             },
-        }
+            Command::Down(x) => Self {
+                depth: self.depth.checked_add(x.into())?,
+                ..self
+            },
+            Command::Up(x) => Self {
+                depth: self.depth.checked_sub(x.into())?,
+                ..self
+            },
+            Command::Surface => Self {
+                depth: T::default(),
+                ..self
+            },
+            Command::SetAim(x) => Self {
+                aim: x.into(),
+                ..self
+            },
+        })
     }
 
-    fn apply_command(self, command: Command) -> Self {
-        match command {
-            Command::AdjustAim(x) => Self {
-                aim: self.aim + x,
+    fn apply_command(self, command: Command) -> Option<Self> {
+        Some(match command {
+            Command::Forward(x) => Self {
+                depth: self.depth.checked_add(self.aim.checked_mul(x.into())?)?,
+                horizontal: self.horizontal.checked_add(x.into())?,
                 ..self
             },
-            Command::MoveForward(x) => Self {
-                depth: self.depth + self.aim * x,
-                horizontal: self.horizontal + x,
+            // Reverses along the same heading
+            Command::Backward(x) => Self {
+                depth: self.depth.checked_sub(self.aim.checked_mul(x.into())?)?,
+                horizontal: self.horizontal.checked_sub(x.into())?,
                 ..self
             },
-        }
+            Command::Down(x) => Self {
+                aim: self.aim.checked_add(x.into())?,
+                ..self
+            },
+            Command::Up(x) => Self {
+                aim: self.aim.checked_sub(x.into())?,
+                ..self
+            },
+            Command::Surface => Self {
+                depth: T::default(),
+                ..self
+            },
+            Command::SetAim(x) => Self {
+                aim: x.into(),
+                ..self
+            },
+        })
     }
 
-    fn result(&self) -> i32 {
-        self.depth * self.horizontal
+    /// The depth times the horizontal position, which is the puzzle answer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the product doesn't fit in a `T`.
+    pub fn result(&self) -> Result<T, Overflow> {
+        self.depth
+            .checked_mul(self.horizontal)
+            .ok_or(Overflow::Result)
     }
 }
 
-fn aggregate_commands<const N: usize>(
-    commands: &[Command; N],
-    apply: fn(SubmarineState, Command) -> SubmarineState,
-) -> SubmarineState {
+/// Where the submarine ends up following `commands`, counting in `T`.
+///
+/// # Errors
+///
+/// Returns an error for the first command after which the state doesn't fit
+/// in a `T`.
+pub fn aggregate_commands<T: Integer>(
+    commands: &[Command],
+    interpreter: &impl Interpreter<T>,
+) -> Result<SubmarineState<T>, Overflow> {
     commands
         .iter()
-        .copied()
-        .fold(SubmarineState::default(), apply)
+        .enumerate()
+        .try_fold(SubmarineState::default(), |state, (index, &command)| {
+            interpreter
+                .apply(state, command)
+                .ok_or(Overflow::Command { index, command })
+        })
 }
 
-#[allow(clippy::needless_range_loop)]
-fn fast_parse<const N: usize>(input: &str) -> [Command; N] {
+/// Parses commands like `forward 12`, `surface` or `set-aim -3`, one per
+/// line. Blank lines and extra whitespace (like the `\r` of Windows line
+/// endings) are skipped.
+///
+/// # Errors
+///
+/// Returns an error pointing at the first command or amount that isn't one.
+pub fn parse_input(input: &str) -> Result<Vec<Command>, ParseError> {
+    let commands = parse_lines(input, |line| {
+        let mut tokens = line.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            return Ok(None);
+        };
+        // Where a missing amount would have been
+        let missing = &keyword[keyword.len()..];
+        let mut amount = |min| {
+            let amount = tokens.next().unwrap_or(missing);
+            amount
+                .parse()
+                .ok()
+                .filter(|&amount| amount >= min)
+                .ok_or_else(|| {
+                    let expected = match min {
+                        0 => format!("an amount from 0 to {}", i32::MAX),
+                        _ => "an aim".to_owned(),
+                    };
+                    ParseError::at(line, amount, expected)
+                })
+        };
+        let command = match keyword {
+            "forward" => Command::Forward(amount(0)?),
+            "backward" => Command::Backward(amount(0)?),
+            "down" => Command::Down(amount(0)?),
+            "up" => Command::Up(amount(0)?),
+            "surface" => Command::Surface,
+            "set-aim" => Command::SetAim(amount(i32::MIN)?),
+            _ => {
+                return Err(ParseError::at(
+                    line,
+                    keyword,
+                    "`forward`, `backward`, `down`, `up`, `surface` or `set-aim`",
+                ))
+            }
+        };
+        match tokens.next() {
+            Some(extra) => Err(ParseError::at(line, extra, "the end of the line")),
+            None => Ok(Some(command)),
+        }
+    })?;
+    Ok(commands.into_iter().flatten().collect())
+}
+
+/// Only knows `forward`, `down` and `up`, only looks at their first letter,
+/// and expects single digit amounts and `\n` line endings, so it's only for
+/// inputs that are known to be well-formed. See [`parse_chunked`] for one
+/// that isn't picky.
+///
+/// # Errors
+///
+/// Returns an error if a line doesn't start with a command, or doesn't end
+/// with a single digit amount.
+pub fn fast_parse(input: &str) -> Result<Vec<Command>, ParseError> {
     let bytes = input.as_bytes();
-    let mut commands: [MaybeUninit<Command>; N] = MaybeUninit::uninit_array();
+    // The shortest command ("up 1\n") is 5 bytes, so this never reallocates
+    let mut commands = Vec::with_capacity(bytes.len() / 5 + 1);
 
     let mut index = 0;
-    for j in 0..commands.len() {
-        match bytes[index] {
-            b'f' => {
-                index += 8;
-                commands[j].write(Command::MoveForward(parse_byte_to_int(bytes[index])));
-                index += 2;
-            }
-            b'd' => {
-                index += 5;
-                commands[j].write(Command::AdjustAim(parse_byte_to_int(bytes[index])));
-                index += 2;
+    while index < bytes.len() {
+        let (command, length): (fn(i32) -> Command, _) = match bytes[index] {
+            b'f' => (Command::Forward, 8),
+            b'd' => (Command::Down, 5),
+            b'u' => (Command::Up, 3),
+            _ => return Err(error_at(input, index, "a command")),
+        };
+        index += length;
+        match (bytes.get(index), bytes.get(index + 1)) {
+            (Some(byte @ b'0'..=b'9'), None | Some(b'\n')) => {
+                commands.push(command(i32::from(byte - b'0')));
             }
-            b'u' => {
-                index += 3;
-                commands[j].write(Command::AdjustAim(-(parse_byte_to_int(bytes[index]))));
-                index += 2;
-            }
-            _ => panic!(),
+            _ => return Err(error_at(input, index, "a single digit amount")),
         }
+        index += 2;
     }
 
-    unsafe { MaybeUninit::array_assume_init(commands) }
+    Ok(commands)
 }
 
-fn parse_byte_to_int(byte: u8) -> i32 {
-    i32::from(byte - b'0')
+// The word starting at byte `index` (or the closest character before it)
+fn error_at(input: &str, index: usize, expected: &str) -> ParseError {
+    let start = (0..=index.min(input.len()))
+        .rev()
+        .find(|&index| input.is_char_boundary(index))
+        .unwrap_or_default();
+    let word = input[start..]
+        .split(char::is_whitespace)
+        .next()
+        .unwrap_or_default();
+    ParseError::at(input, word, expected)
+}
+
+#[derive(Debug)]
+pub enum DiveError {
+    InvalidInput(ParseError),
+    Overflow(Overflow),
+}
+
+impl From<ParseError> for DiveError {
+    fn from(error: ParseError) -> Self {
+        Self::InvalidInput(error)
+    }
+}
+
+impl From<Overflow> for DiveError {
+    fn from(overflow: Overflow) -> Self {
+        Self::Overflow(overflow)
+    }
+}
+
+impl Display for DiveError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::InvalidInput(error) => write!(formatter, "{error}"),
+            Self::Overflow(overflow) => write!(formatter, "{overflow}"),
+        }
+    }
+}
+
+impl Error for DiveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidInput(error) => Some(error),
+            Self::Overflow(overflow) => Some(overflow),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use aoc_core::{mutate, Part, Rng};
+
+    const INPUT: &str = include_str!("commands.txt");
 
     #[test]
     fn sample_1() {
         // Arrange
-        let commands: [_; 6] = fast_parse("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2");
+        let commands = fast_parse("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2").unwrap();
 
         // Act
-        let state = aggregate_commands(&commands, SubmarineState::apply_command_incorrectly);
+        let state =
+            aggregate_commands(&commands, &SubmarineState::<i64>::apply_command_incorrectly)
+                .unwrap();
 
         // Assert
         assert_eq!(state.depth, 10);
         assert_eq!(state.horizontal, 15);
-        assert_eq!(state.result(), 150);
+        assert_eq!(state.result(), Ok(150));
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&fast_parse(INPUT)), 2_147_104);
+        assert_eq!(part_1(&fast_parse(INPUT).unwrap()), Ok(2_147_104));
     }
 
     #[test]
     fn sample_2() {
         // Arrange
-        let commands: [_; 6] = fast_parse("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2");
+        let commands = fast_parse("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2").unwrap();
 
         // Act
-        let state = aggregate_commands(&commands, SubmarineState::apply_command);
+        let state = aggregate_commands(&commands, &SubmarineState::<i64>::apply_command).unwrap();
 
         // Assert
         assert_eq!(state.depth, 60);
         assert_eq!(state.horizontal, 15);
-        assert_eq!(state.result(), 900);
+        assert_eq!(state.result(), Ok(900));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&fast_parse(INPUT)), 2_044_620_088);
+        assert_eq!(part_2(&fast_parse(INPUT).unwrap()), Ok(2_044_620_088));
     }
 
-    #[bench]
-    fn bench_parse(b: &mut Bencher) {
-        b.iter(|| {
-            let commands: [_; 1000] = fast_parse(INPUT);
-            commands
-        });
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input("forward 12\r\n\n  down 2147483647 \nup 0\n\n"),
+            Ok(vec![
+                Command::Forward(12),
+                Command::Down(i32::MAX),
+                Command::Up(0)
+            ])
+        );
+        assert_eq!(parse_input(INPUT), fast_parse(INPUT));
+        assert_eq!(parse_input(""), Ok(vec![]));
+        assert_eq!(
+            parse_input("backward 3\nsurface\nset-aim -7\nset-aim 2\n"),
+            Ok(vec![
+                Command::Backward(3),
+                Command::Surface,
+                Command::SetAim(-7),
+                Command::SetAim(2)
+            ])
+        );
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        let c = fast_parse(INPUT);
-        b.iter(|| part_1(&c));
+    #[test]
+    fn test_parse_input_errors() {
+        let parse_error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            parse_error("forward 5\ndive 2\n"),
+            "line 2, column 1: expected `forward`, `backward`, `down`, `up`, `surface` or \
+             `set-aim`, found `dive`"
+        );
+        assert_eq!(
+            parse_error("down 5\nup 2147483648\n"),
+            "line 2, column 4: expected an amount from 0 to 2147483647, found `2147483648`"
+        );
+        assert_eq!(
+            parse_error("\nforward"),
+            "line 2, column 8: expected an amount from 0 to 2147483647, found nothing"
+        );
+        assert_eq!(
+            parse_error("up -3"),
+            "line 1, column 4: expected an amount from 0 to 2147483647, found `-3`"
+        );
+        assert_eq!(
+            parse_error("up 3 down 4"),
+            "line 1, column 6: expected the end of the line, found `down`"
+        );
+        assert_eq!(
+            parse_error("surface 3"),
+            "line 1, column 9: expected the end of the line, found `3`"
+        );
+        assert_eq!(
+            parse_error("set-aim"),
+            "line 1, column 8: expected an aim, found nothing"
+        );
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        let c = fast_parse(INPUT);
-        b.iter(|| part_2(&c));
+    // Whatever `fast_parse` can parse, `parse_input` parses the same way
+    #[test]
+    fn test_agrees_with_fast_parse() {
+        let mut rng = Rng::new(0);
+        for size in [0, 1, 10, 1_000] {
+            let input = generate(&mut rng, size);
+            assert_eq!(parse_input(&input), Ok(fast_parse(&input).unwrap()));
+            let without_last_newline = input.strip_suffix('\n').unwrap_or_default();
+            assert_eq!(
+                parse_input(without_last_newline),
+                fast_parse(without_last_newline)
+            );
+        }
+
+        // `fast_parse` only looks at the first letter of each command, so on
+        // broken inputs they can only agree when both work
+        let inputs: Vec<_> = (0..10).map(|_| generate(&mut rng, 5)).collect();
+        for _ in 0..10_000 {
+            let valid = rng.choose(&inputs);
+            let input = mutate(&mut rng, valid);
+            if let (Ok(fast), Ok(robust)) = (fast_parse(&input), parse_input(&input)) {
+                assert_eq!(fast, robust, "{input:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_extended_commands() {
+        let commands =
+            parse_input("down 2\nforward 5\nbackward 2\nset-aim -1\nforward 4\nsurface\nup 3\n")
+                .unwrap();
+        assert_eq!(
+            aggregate_commands(&commands, &Interpretation::Depth),
+            Ok(SubmarineState {
+                depth: -3,
+                horizontal: 7,
+                aim: -1
+            })
+        );
+        assert_eq!(
+            aggregate_commands(&commands, &Interpretation::Aim),
+            Ok(SubmarineState {
+                depth: 0,
+                horizontal: 7,
+                aim: -4
+            })
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let commands = parse_input("down 2147483647\nforward 1\nforward 2\n").unwrap();
+        assert_eq!(
+            aggregate_commands::<i32>(&commands, &Interpretation::Aim),
+            Err(Overflow::Command {
+                index: 2,
+                command: Command::Forward(2)
+            })
+        );
+        let state = aggregate_commands::<i64>(&commands, &Interpretation::Aim).unwrap();
+        assert_eq!(state.depth, 3 * i64::from(i32::MAX));
+        assert_eq!(state.result(), Ok(9 * i64::from(i32::MAX)));
+
+        // Every position fits, but their product doesn't
+        let commands = parse_input("down 100000\nforward 100000\n").unwrap();
+        let state = aggregate_commands::<i32>(&commands, &Interpretation::Depth).unwrap();
+        assert_eq!(state.result(), Err(Overflow::Result));
+        assert_eq!(part_1(&commands), Ok(10_000_000_000));
+
+        let input = "down 2147483647\nforward 2147483647\n".repeat(3);
+        assert_eq!(
+            Day02::solve(&input, Part::Two).unwrap_err().to_string(),
+            "command 3 (`forward 2147483647`) overflows"
+        );
+        let commands = parse_input(&input).unwrap();
+        let state = aggregate_commands::<i128>(&commands, &Interpretation::Aim).unwrap();
+        assert_eq!(state.depth, 6 * i128::from(i32::MAX).pow(2));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let commands = parse_input(INPUT).unwrap();
+        let displayed = commands
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(displayed, INPUT.trim_end());
+        assert_eq!(parse_input(&displayed), Ok(commands));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            fast_parse("forward 5\nbackward 2\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: expected a command, found `backward`"
+        );
+        assert_eq!(
+            fast_parse("down 5\nup x\n").unwrap_err().to_string(),
+            "line 2, column 4: expected a single digit amount, found `x`"
+        );
+        assert_eq!(
+            fast_parse("forward").unwrap_err().to_string(),
+            "line 1, column 8: expected a single digit amount, found nothing"
+        );
+        assert_eq!(
+            fast_parse("down 5\nup 75").unwrap_err().to_string(),
+            "line 2, column 4: expected a single digit amount, found `75`"
+        );
     }
 }
@@ -0,0 +1,108 @@
+use std::io::{self, Write};
+
+use crate::{Command, Integer, Interpreter, Overflow, SubmarineState};
+
+/// The state of the submarine after command `index`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step<T = i64> {
+    pub index: usize,
+    pub command: Command,
+    pub state: SubmarineState<T>,
+}
+
+/// Every state the submarine goes through following `commands`, one step
+/// per command, counting in `T`.
+///
+/// If a state doesn't fit in a `T`, the last item is the overflow.
+pub fn trajectory<'a, T: Integer + 'a>(
+    commands: &'a [Command],
+    interpreter: impl Interpreter<T> + 'a,
+) -> impl Iterator<Item = Result<Step<T>, Overflow>> + 'a {
+    commands.iter().enumerate().scan(
+        Some(SubmarineState::default()),
+        move |state, (index, &command)| {
+            // Nothing comes after an overflow
+            *state = interpreter.apply((*state)?, command);
+            Some(match *state {
+                Some(state) => Ok(Step {
+                    index,
+                    command,
+                    state,
+                }),
+                None => Err(Overflow::Command { index, command }),
+            })
+        },
+    )
+}
+
+/// Writes `steps` as CSV, with a header row, for plotting the dive.
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_csv<T: Integer>(
+    steps: impl IntoIterator<Item = Step<T>>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    writeln!(writer, "step,command,depth,horizontal,aim")?;
+    for Step {
+        index,
+        command,
+        state,
+    } in steps
+    {
+        writeln!(
+            writer,
+            "{index},{command},{},{},{}",
+            state.depth, state.horizontal, state.aim
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aggregate_commands, parse_input, Interpretation};
+
+    const SAMPLE_INPUT: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+
+    #[test]
+    fn test_trajectory() {
+        let commands = parse_input(SAMPLE_INPUT).unwrap();
+        for interpretation in [Interpretation::Depth, Interpretation::Aim] {
+            let steps: Vec<Step> = trajectory(&commands, interpretation)
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(steps.len(), commands.len());
+            assert_eq!(steps[2].command, Command::Forward(8));
+            assert_eq!(
+                Ok(steps.last().unwrap().state),
+                aggregate_commands(&commands, &interpretation)
+            );
+        }
+
+        // The first `down` dives in part 1, but only aims in part 2
+        let diverges_at = trajectory::<i64>(&commands, Interpretation::Depth)
+            .zip(trajectory(&commands, Interpretation::Aim))
+            .position(|(depth, aim)| depth.unwrap().state.depth != aim.unwrap().state.depth);
+        assert_eq!(diverges_at, Some(1));
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let commands = parse_input(SAMPLE_INPUT).unwrap();
+        let mut csv = Vec::new();
+        let steps = trajectory::<i64>(&commands[..4], Interpretation::Aim).map(Result::unwrap);
+        write_csv(steps, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "step,command,depth,horizontal,aim
+0,forward 5,0,5,0
+1,down 5,0,5,5
+2,forward 8,40,13,5
+3,up 3,40,13,2
+"
+        );
+    }
+}
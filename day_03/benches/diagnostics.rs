@@ -0,0 +1,66 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+//! Compares the packed `Diagnostics` with the original string based
+//! solution on generated reports of different sizes.
+//!
+//! Run with `cargo bench -p day_03`.
+
+use aoc_core::{measure, Rng};
+use day_03::{find_rate, find_rating, generate, parse_input, BitCriteria, Diagnostics};
+use std::{hint::black_box, time::Duration};
+
+const SIZES: [usize; 3] = [1_000, 100_000, 1_000_000];
+
+// Roughly how long to spend measuring each step on each size
+const BUDGET: Duration = Duration::from_secs(2);
+
+fn main() {
+    for size in SIZES {
+        let input = generate(&mut Rng::new(3), size);
+        let diagnostics = Diagnostics::parse(&input).unwrap();
+        let width = diagnostics.width();
+        println!("{size} numbers:");
+
+        compare(
+            "parse",
+            || parse_input(black_box(&input)).map(|numbers| numbers.len()),
+            || Diagnostics::parse(black_box(&input)).map(|diagnostics| diagnostics.width()),
+        );
+        // The packed rates and ratings come from counting and sorting while
+        // parsing, so they're only compared with parsing included
+        compare(
+            "part 1",
+            || {
+                let numbers = parse_input(black_box(&input)).unwrap();
+                find_rate(width, &numbers, BitCriteria::MOST_COMMON).unwrap()
+                    * find_rate(width, &numbers, BitCriteria::LEAST_COMMON).unwrap()
+            },
+            || {
+                let diagnostics = Diagnostics::parse(black_box(&input)).unwrap();
+                diagnostics.gamma_rate() * diagnostics.epsilon_rate()
+            },
+        );
+        compare(
+            "part 2",
+            || {
+                let numbers = parse_input(black_box(&input)).unwrap();
+                find_rating(width, &numbers, BitCriteria::MOST_COMMON).unwrap()
+                    * find_rating(width, &numbers, BitCriteria::LEAST_COMMON).unwrap()
+            },
+            || {
+                let diagnostics = Diagnostics::parse(black_box(&input)).unwrap();
+                diagnostics.oxygen_generator_rating().unwrap()
+                    * diagnostics.co2_scrubber_rating().unwrap()
+            },
+        );
+    }
+}
+
+fn compare<S, P>(name: &str, strings: impl FnMut() -> S, packed: impl FnMut() -> P) {
+    let strings = measure(BUDGET, strings).median;
+    let packed = measure(BUDGET, packed).median;
+    println!(
+        "    {name:<8} strings {strings:>10.2?}, packed {packed:>10.2?} ({:.1}x)",
+        strings.as_secs_f64() / packed.as_secs_f64()
+    );
+}
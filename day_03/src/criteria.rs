@@ -0,0 +1,69 @@
+use std::cmp::Ordering;
+
+/// Which bit to keep in a column, from how many numbers have each.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BitCriteria {
+    MostCommon(TieBreak),
+    LeastCommon(TieBreak),
+}
+
+/// Which bit wins when a column has as many 1s as 0s, if any.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TieBreak {
+    One,
+    Zero,
+    Error,
+}
+
+impl BitCriteria {
+    /// The gamma rate's and the oxygen generator rating's.
+    pub const MOST_COMMON: Self = Self::MostCommon(TieBreak::One);
+    /// The epsilon rate's and the CO2 scrubber rating's.
+    pub const LEAST_COMMON: Self = Self::LeastCommon(TieBreak::Zero);
+
+    /// Whether to keep the numbers with a 1, or `None` on a tie that's an
+    /// error.
+    #[must_use]
+    pub fn keep_ones(self, ones: usize, zeros: usize) -> Option<bool> {
+        match (self, ones.cmp(&zeros)) {
+            (Self::MostCommon(tie) | Self::LeastCommon(tie), Ordering::Equal) => match tie {
+                TieBreak::One => Some(true),
+                TieBreak::Zero => Some(false),
+                TieBreak::Error => None,
+            },
+            (Self::MostCommon(_), order) => Some(order == Ordering::Greater),
+            (Self::LeastCommon(_), order) => Some(order == Ordering::Less),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_ones() {
+        assert_eq!(BitCriteria::MOST_COMMON.keep_ones(3, 2), Some(true));
+        assert_eq!(BitCriteria::MOST_COMMON.keep_ones(2, 2), Some(true));
+        assert_eq!(BitCriteria::LEAST_COMMON.keep_ones(3, 2), Some(false));
+        assert_eq!(BitCriteria::LEAST_COMMON.keep_ones(2, 2), Some(false));
+        // Even when there are no numbers with a 1 to keep
+        assert_eq!(BitCriteria::LEAST_COMMON.keep_ones(0, 2), Some(true));
+        assert_eq!(
+            BitCriteria::MostCommon(TieBreak::Zero).keep_ones(1, 1),
+            Some(false)
+        );
+        assert_eq!(
+            BitCriteria::LeastCommon(TieBreak::One).keep_ones(1, 1),
+            Some(true)
+        );
+        assert_eq!(
+            BitCriteria::LeastCommon(TieBreak::Error).keep_ones(1, 1),
+            None
+        );
+        assert_eq!(
+            BitCriteria::MostCommon(TieBreak::Error).keep_ones(0, 1),
+            Some(false)
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use aoc_core::Rng;
+use std::fmt::Write;
+
+const MIN_BITS: usize = 12;
+
+/// A diagnostic report of `size` distinct binary numbers (but at least two),
+/// 12 bits wide, or wider when that's too few for them.
+///
+/// Whenever more than one number is left while looking for the CO2 scrubber
+/// rating, they don't all have the same next bit, so the rating always exists.
+/// The numbers are distinct, so neither rating ends up with duplicates.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    // Only a quarter of the numbers get used, so picking unused ones is quick
+    let num_bits = MIN_BITS.max((4 * size).next_power_of_two().trailing_zeros() as usize);
+    let mut used = vec![false; 1 << num_bits];
+    let mut numbers = Vec::with_capacity(size);
+    while numbers.len() < size {
+        let number = rng.below(1 << num_bits);
+        if !std::mem::replace(&mut used[number], true) {
+            numbers.push(number);
+        }
+    }
+
+    // Flipping a bit only changes the candidates from this column on, so the
+    // earlier columns stay fine. The candidates are all the numbers starting
+    // with their bits, so the flipped number isn't one of the others.
+    let mut candidates: Vec<usize> = (0..size).collect();
+    for column in 0..num_bits {
+        if candidates.len() <= 1 {
+            break;
+        }
+        let mask = 1 << (num_bits - 1 - column);
+        let mut num_ones = candidates
+            .iter()
+            .filter(|&&index| numbers[index] & mask != 0)
+            .count();
+        if num_ones == 0 || num_ones == candidates.len() {
+            let index = *rng.choose(&candidates);
+            numbers[index] ^= mask;
+            num_ones = if num_ones == 0 { 1 } else { num_ones - 1 };
+        }
+        let least_common_bit = if 2 * num_ones >= candidates.len() {
+            0
+        } else {
+            mask
+        };
+        candidates.retain(|&index| numbers[index] & mask == least_common_bit);
+    }
+
+    let mut input = String::with_capacity((num_bits + 1) * size);
+    for number in numbers {
+        writeln!(input, "{number:0num_bits$b}").expect("writing to a String can't fail");
+    }
+    input
+}
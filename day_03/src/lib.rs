@@ -1,74 +1,303 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
 
-fn part_1(input: &str) -> usize {
-    let num_columns = input.lines().next().unwrap().chars().count();
-    let numbers: Vec<_> = input.lines().collect();
-    let gamma_rate = find_rate(num_columns, &numbers, most_common_bit);
-    let epsilon_rate = find_rate(num_columns, &numbers, least_common_bit);
-    gamma_rate * epsilon_rate
+mod criteria;
+mod generate;
+mod packed;
+mod report;
+
+use aoc_core::{parse_lines, Answer, ParseError, Solution};
+pub use criteria::{BitCriteria, TieBreak};
+pub use generate::generate;
+pub use packed::{Diagnostics, RatingError, MAX_WIDTH};
+pub use report::{BitCounts, DiagnosticReport, RatingTrace, Step};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Diagnostics;
+    type Error = DiagnosticError;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(Diagnostics::parse(input)?)
+    }
+
+    fn part_1(diagnostics: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(diagnostics)?.into())
+    }
+
+    fn part_2(diagnostics: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(diagnostics)?.into())
+    }
+}
+
+fn part_1(diagnostics: &Diagnostics) -> Result<i128, DiagnosticError> {
+    let no_rate = |rate| move |error| DiagnosticError::NoRate { rate, error };
+    let gamma_rate = diagnostics
+        .rate_by(BitCriteria::MOST_COMMON)
+        .map_err(no_rate("gamma"))?;
+    let epsilon_rate = diagnostics
+        .rate_by(BitCriteria::LEAST_COMMON)
+        .map_err(no_rate("epsilon"))?;
+    multiply(gamma_rate, epsilon_rate)
+}
+
+fn part_2(diagnostics: &Diagnostics) -> Result<i128, DiagnosticError> {
+    let no_rating = |rating| move |error| DiagnosticError::NoRating { rating, error };
+    let oxygen_generator_rating = diagnostics
+        .oxygen_generator_rating()
+        .map_err(no_rating("oxygen generator"))?;
+    let co2_scrubber_rating = diagnostics
+        .co2_scrubber_rating()
+        .map_err(no_rating("CO2 scrubber"))?;
+    multiply(oxygen_generator_rating, co2_scrubber_rating)
+}
+
+// The answers are products, so they can be twice as wide as the numbers
+fn multiply(a: u128, b: u128) -> Result<i128, DiagnosticError> {
+    a.checked_mul(b)
+        .and_then(|product| i128::try_from(product).ok())
+        .ok_or(DiagnosticError::Overflow)
 }
 
-fn part_2(input: &str) -> usize {
-    let num_columns = input.lines().next().unwrap().chars().count();
-    let numbers = input.lines().collect::<Vec<_>>();
-    let oxygen_generator_rating = find_rating(num_columns, &numbers, most_common_bit);
-    let co2_scrubber_rating = find_rating(num_columns, &numbers, least_common_bit);
+// The original, string based solution, which `Diagnostics` is checked and
+// benchmarked against
 
-    oxygen_generator_rating * co2_scrubber_rating
+/// Parses binary numbers, one per line, without converting them.
+///
+/// # Errors
+///
+/// Returns an error pointing at the first line that isn't one, or isn't as
+/// wide as the first one.
+pub fn parse_input(input: &str) -> Result<Vec<&str>, ParseError> {
+    let width = input.lines().next().map_or(0, str::len);
+    parse_lines(input, |line| {
+        if line.is_empty() || !line.bytes().all(|byte| matches!(byte, b'0' | b'1')) {
+            Err(ParseError::at(line, line, "a binary number"))
+        } else if line.len() != width {
+            Err(ParseError::at(
+                line,
+                line,
+                format!("a binary number of {width} bits, like the first one"),
+            ))
+        } else {
+            Ok(line)
+        }
+    })
 }
 
-fn find_rate(num_columns: usize, numbers: &[&str], choose_bit: fn(&[&str], usize) -> u8) -> usize {
-    usize::from_str_radix(
-        &(0..num_columns)
-            .map(|column| char::from(choose_bit(numbers, column)))
-            .collect::<String>(),
-        2,
-    )
-    .unwrap()
+/// Builds a number from the bit `criteria` picks in each column.
+///
+/// # Errors
+///
+/// Returns an error if a number isn't `num_columns` binary digits, there's a
+/// tie `criteria` doesn't break, or the rate doesn't fit in a `usize`.
+pub fn find_rate(
+    num_columns: usize,
+    numbers: &[&str],
+    criteria: BitCriteria,
+) -> Result<usize, NumbersError> {
+    check_numbers(num_columns, numbers)?;
+    (0..num_columns).try_fold(0, |rate: usize, column| {
+        let (ones, zeros) = count_bits(numbers, column);
+        let one = criteria
+            .keep_ones(ones, zeros)
+            .ok_or(NumbersError::Tie { column })?;
+        rate.checked_mul(2)
+            .map(|rate| rate | usize::from(one))
+            .ok_or(NumbersError::Overflow)
+    })
 }
 
-fn find_rating(
+/// Keeps the numbers with the bit `criteria` picks in each column until only
+/// one is left.
+///
+/// # Errors
+///
+/// Returns an error if a number isn't `num_columns` binary digits, there's a
+/// tie `criteria` doesn't break, there's no single rating left, or it doesn't
+/// fit in a `usize`.
+pub fn find_rating(
     num_columns: usize,
     numbers: &[&str],
-    bit_criteria: impl Fn(&[&str], usize) -> u8,
-) -> usize {
+    criteria: BitCriteria,
+) -> Result<usize, NumbersError> {
+    check_numbers(num_columns, numbers)?;
     let mut candidate_numbers = Vec::from(numbers);
     for column in 0..num_columns {
-        let bit_criteria = bit_criteria(&candidate_numbers, column);
-        candidate_numbers.retain(|line| line.as_bytes()[column] == bit_criteria);
-        if candidate_numbers.len() == 1 {
+        if candidate_numbers.len() <= 1 {
             break;
         }
+        let (ones, zeros) = count_bits(&candidate_numbers, column);
+        let bit = match criteria.keep_ones(ones, zeros) {
+            Some(true) => b'1',
+            Some(false) => b'0',
+            None => return Err(NumbersError::Tie { column }),
+        };
+        candidate_numbers.retain(|number| number.as_bytes()[column] == bit);
+        if candidate_numbers.is_empty() {
+            return Err(RatingError::NoneLeft { column }.into());
+        }
     }
 
-    usize::from_str_radix(candidate_numbers[0], 2).unwrap()
+    match candidate_numbers[..] {
+        [] => Err(RatingError::Empty.into()),
+        [number] => from_binary(number).ok_or(NumbersError::Overflow),
+        [number, ..] => Err(RatingError::Duplicates {
+            number: from_binary(number).ok_or(NumbersError::Overflow)? as u128,
+            count: candidate_numbers.len(),
+        }
+        .into()),
+    }
 }
 
-fn most_common_bit(numbers: &[&str], column: usize) -> u8 {
-    let mut num_zeros = 0;
-    let mut num_ones = 0;
+// Every number has to be `num_columns` binary digits, so the columns can be
+// indexed
+fn check_numbers(num_columns: usize, numbers: &[&str]) -> Result<(), NumbersError> {
+    for (index, number) in numbers.iter().enumerate() {
+        if number.len() != num_columns {
+            return Err(NumbersError::Ragged {
+                index,
+                width: number.len(),
+                expected: num_columns,
+            });
+        }
+        if let Some(column) = number.bytes().position(|byte| !matches!(byte, b'0' | b'1')) {
+            return Err(NumbersError::NotBinary { index, column });
+        }
+    }
+    Ok(())
+}
+
+// How many of `numbers` have a 1 and a 0 in `column`
+fn count_bits(numbers: &[&str], column: usize) -> (usize, usize) {
+    let ones = numbers
+        .iter()
+        .filter(|number| number.as_bytes()[column] == b'1')
+        .count();
+    (ones, numbers.len() - ones)
+}
 
-    for number in numbers {
-        match number.as_bytes()[column] {
-            b'0' => num_zeros += 1,
-            b'1' => num_ones += 1,
-            _ => panic!(),
+// `None` if it doesn't fit
+fn from_binary(number: &str) -> Option<usize> {
+    number.bytes().try_fold(0, |value: usize, bit| {
+        value
+            .checked_mul(2)
+            .map(|value| value | usize::from(bit == b'1'))
+    })
+}
+
+/// Why [`find_rate`] or [`find_rating`] failed. Numbers and columns count
+/// from 0.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NumbersError {
+    /// Number `index` is `width` characters long, not `expected`.
+    Ragged {
+        index: usize,
+        width: usize,
+        expected: usize,
+    },
+    /// Number `index` has something other than `0` or `1` in `column`.
+    NotBinary {
+        index: usize,
+        column: usize,
+    },
+    /// As many numbers have a 1 as a 0 in `column`, and that's an error.
+    Tie {
+        column: usize,
+    },
+    Rating(RatingError),
+    /// The result doesn't fit in a `usize`.
+    Overflow,
+}
+
+impl From<RatingError> for NumbersError {
+    fn from(error: RatingError) -> Self {
+        Self::Rating(error)
+    }
+}
+
+impl Display for NumbersError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Ragged {
+                index,
+                width,
+                expected,
+            } => write!(
+                formatter,
+                "number {index} is {width} characters long instead of {expected}"
+            ),
+            Self::NotBinary { index, column } => write!(
+                formatter,
+                "number {index} has something other than 0 or 1 in column {column}"
+            ),
+            Self::Tie { column } => {
+                write!(
+                    formatter,
+                    "as many numbers have a 1 as a 0 in column {column}"
+                )
+            }
+            Self::Rating(error) => write!(formatter, "{error}"),
+            Self::Overflow => write!(formatter, "the result doesn't fit in a usize"),
         }
     }
+}
 
-    if num_zeros > num_ones {
-        b'0'
-    } else {
-        b'1'
+impl Error for NumbersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Rating(error) => Some(error),
+            _ => None,
+        }
     }
 }
 
-fn least_common_bit(numbers: &[&str], column: usize) -> u8 {
-    match most_common_bit(numbers, column) {
-        b'0' => b'1',
-        b'1' => b'0',
-        _ => panic!(),
+#[derive(Debug)]
+pub enum DiagnosticError {
+    InvalidInput(ParseError),
+    /// The named rate can't be found.
+    NoRate {
+        rate: &'static str,
+        error: RatingError,
+    },
+    /// The named rating can't be found.
+    NoRating {
+        rating: &'static str,
+        error: RatingError,
+    },
+    /// The answer doesn't fit in an `i128`.
+    Overflow,
+}
+
+impl From<ParseError> for DiagnosticError {
+    fn from(error: ParseError) -> Self {
+        Self::InvalidInput(error)
+    }
+}
+
+impl Display for DiagnosticError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::InvalidInput(error) => write!(formatter, "{error}"),
+            Self::NoRate { rate, error } => write!(formatter, "no {rate} rate: {error}"),
+            Self::NoRating { rating, error } => write!(formatter, "no {rating} rating: {error}"),
+            Self::Overflow => write!(formatter, "the answer is too large"),
+        }
+    }
+}
+
+impl Error for DiagnosticError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidInput(error) => Some(error),
+            Self::NoRate { error, .. } | Self::NoRating { error, .. } => Some(error),
+            Self::Overflow => None,
+        }
     }
 }
 
@@ -81,21 +310,138 @@ mod tests {
 
     #[test]
     fn test_sample_1() {
-        assert_eq!(part_1(SAMPLE_INPUT), 198);
+        assert_eq!(
+            part_1(&Diagnostics::parse(SAMPLE_INPUT).unwrap()).unwrap(),
+            198
+        );
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(INPUT), 3_277_364);
+        assert_eq!(
+            part_1(&Diagnostics::parse(INPUT).unwrap()).unwrap(),
+            3_277_364
+        );
     }
 
     #[test]
     fn test_sample_2() {
-        assert_eq!(part_2(SAMPLE_INPUT), 230);
+        assert_eq!(
+            part_2(&Diagnostics::parse(SAMPLE_INPUT).unwrap()).unwrap(),
+            230
+        );
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(INPUT), 5_736_383);
+        assert_eq!(
+            part_2(&Diagnostics::parse(INPUT).unwrap()).unwrap(),
+            5_736_383
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        // Both are left for the CO2 scrubber rating, but they both start with
+        // a 1
+        let diagnostics = Diagnostics::parse("11\n10\n").unwrap();
+        assert_eq!(
+            part_2(&diagnostics).unwrap_err().to_string(),
+            "no CO2 scrubber rating: no number is left after column 0"
+        );
+
+        // Gamma is the number itself, and epsilon the other half of the bits
+        let wide = format!("{}{}", "1".repeat(64), "0".repeat(64));
+        assert!(matches!(
+            part_1(&Diagnostics::parse(&wide).unwrap()),
+            Err(DiagnosticError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_strings() {
+        let numbers = parse_input(SAMPLE_INPUT).unwrap();
+        let rate = |criteria| find_rate(5, &numbers, criteria);
+        let rating = |criteria| find_rating(5, &numbers, criteria);
+        assert_eq!(rate(BitCriteria::MOST_COMMON), Ok(22));
+        assert_eq!(rate(BitCriteria::LEAST_COMMON), Ok(9));
+        assert_eq!(rating(BitCriteria::MOST_COMMON), Ok(23));
+        assert_eq!(rating(BitCriteria::LEAST_COMMON), Ok(10));
+
+        // The oxygen generator rating ends on a tie, and the CO2 scrubber
+        // rating has one in column 2
+        assert_eq!(rating(BitCriteria::MostCommon(TieBreak::Zero)), Ok(22));
+        assert_eq!(
+            rating(BitCriteria::MostCommon(TieBreak::Error)),
+            Err(NumbersError::Tie { column: 4 })
+        );
+        assert_eq!(rating(BitCriteria::LeastCommon(TieBreak::One)), Ok(15));
+        assert_eq!(
+            rating(BitCriteria::LeastCommon(TieBreak::Error)),
+            Err(NumbersError::Tie { column: 2 })
+        );
+        assert_eq!(
+            rate(BitCriteria::MostCommon(TieBreak::Error)),
+            Ok(22),
+            "no column has a tie"
+        );
+        assert_eq!(
+            Diagnostics::parse(SAMPLE_INPUT)
+                .unwrap()
+                .rating_by(BitCriteria::LeastCommon(TieBreak::Error)),
+            Err(RatingError::Tie { column: 2 })
+        );
+    }
+
+    #[test]
+    fn test_string_errors() {
+        assert_eq!(
+            find_rate(3, &["101", "11", "011"], BitCriteria::MOST_COMMON),
+            Err(NumbersError::Ragged {
+                index: 1,
+                width: 2,
+                expected: 3
+            })
+        );
+        assert_eq!(
+            find_rating(2, &["10", "1x"], BitCriteria::MOST_COMMON)
+                .unwrap_err()
+                .to_string(),
+            "number 1 has something other than 0 or 1 in column 1"
+        );
+        assert_eq!(
+            find_rating(2, &["11", "10"], BitCriteria::LEAST_COMMON),
+            Err(NumbersError::Rating(RatingError::NoneLeft { column: 0 }))
+        );
+        assert_eq!(
+            find_rating(2, &["01", "01"], BitCriteria::MOST_COMMON),
+            Err(NumbersError::Rating(RatingError::Duplicates {
+                number: 1,
+                count: 2
+            }))
+        );
+        assert_eq!(
+            find_rating(0, &[], BitCriteria::MOST_COMMON),
+            Err(NumbersError::Rating(RatingError::Empty))
+        );
+        let wide = "1".repeat(usize::BITS as usize + 1);
+        assert_eq!(
+            find_rate(wide.len(), &[&wide], BitCriteria::MOST_COMMON),
+            Err(NumbersError::Overflow)
+        );
+        assert_eq!(
+            find_rating(wide.len(), &[&wide], BitCriteria::MOST_COMMON),
+            Err(NumbersError::Overflow)
+        );
+
+        for parse_error in [
+            parse_input("101\n11\n").unwrap_err().to_string(),
+            Diagnostics::parse("101\n11\n").unwrap_err().to_string(),
+        ] {
+            assert_eq!(
+                parse_error,
+                "line 2, column 1: expected a binary number of 3 bits, like the first one, found `11`"
+            );
+        }
     }
 }
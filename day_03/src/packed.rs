@@ -0,0 +1,421 @@
+use crate::{report::Step, BitCriteria};
+use aoc_core::{parse_lines, ParseError};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+/// The widest numbers that fit in a [`Diagnostics`].
+pub const MAX_WIDTH: usize = 128;
+
+/// The numbers of a diagnostic report, parsed once into integers.
+///
+/// Columns are counted from the left, so column 0 is the most significant
+/// bit.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Diagnostics {
+    width: usize,
+    numbers: Vec<u128>,
+    // How many numbers have a 1 in each column
+    ones: Vec<usize>,
+    // The numbers that start with the same bits are next to each other, so
+    // the candidates for a rating are always a range
+    sorted: Vec<u128>,
+}
+
+impl Diagnostics {
+    /// Parses binary numbers of up to [`MAX_WIDTH`] bits, one per line, all
+    /// as wide as the first one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error pointing at the first line that isn't one.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, str::len);
+        let numbers = parse_lines(input, |line| {
+            if line.is_empty()
+                || line.len() > MAX_WIDTH
+                || !line.bytes().all(|byte| matches!(byte, b'0' | b'1'))
+            {
+                return Err(ParseError::at(
+                    line,
+                    line,
+                    format!("a binary number of up to {MAX_WIDTH} bits"),
+                ));
+            }
+            if line.len() != width {
+                return Err(ParseError::at(
+                    line,
+                    line,
+                    format!("a binary number of {width} bits, like the first one"),
+                ));
+            }
+            Ok(line
+                .bytes()
+                .fold(0, |number, byte| number << 1 | u128::from(byte - b'0')))
+        })?;
+        Ok(Self::new(width, numbers))
+    }
+
+    /// Packs `numbers` that are `width` bits wide, ignoring any higher bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is more than [`MAX_WIDTH`].
+    pub fn new(width: usize, numbers: impl IntoIterator<Item = u128>) -> Self {
+        assert!(width <= MAX_WIDTH, "numbers are at most {MAX_WIDTH} bits");
+        let numbers: Vec<_> = numbers
+            .into_iter()
+            .map(|number| number & mask(width))
+            .collect();
+
+        let ones = count_ones(width, &numbers);
+        let sorted = radix_sort(width, &numbers);
+        Self {
+            width,
+            numbers,
+            ones,
+            sorted,
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn numbers(&self) -> &[u128] {
+        &self.numbers
+    }
+
+    /// How many numbers have a 1 in each column.
+    #[must_use]
+    pub fn ones(&self) -> &[usize] {
+        &self.ones
+    }
+
+    /// The most common bit of every column, preferring 1 on ties.
+    #[must_use]
+    pub fn gamma_rate(&self) -> u128 {
+        self.rate_by(BitCriteria::MOST_COMMON)
+            .unwrap_or_else(|_| unreachable!("ties are broken"))
+    }
+
+    /// The least common bit of every column, preferring 0 on ties.
+    #[must_use]
+    pub fn epsilon_rate(&self) -> u128 {
+        self.rate_by(BitCriteria::LEAST_COMMON)
+            .unwrap_or_else(|_| unreachable!("ties are broken"))
+    }
+
+    /// Builds a number from the bit `criteria` picks in each column.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's a tie `criteria` doesn't break.
+    pub fn rate_by(&self, criteria: BitCriteria) -> Result<u128, RatingError> {
+        let len = self.numbers.len();
+        self.ones
+            .iter()
+            .enumerate()
+            .try_fold(0, |rate, (column, &ones)| {
+                let one = criteria
+                    .keep_ones(ones, len - ones)
+                    .ok_or(RatingError::Tie { column })?;
+                Ok(rate << 1 | u128::from(one))
+            })
+    }
+
+    /// Keeps the numbers with the most common bit in each column (1 on ties)
+    /// until only one is left.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no numbers, or more than one are left
+    /// after the last column.
+    pub fn oxygen_generator_rating(&self) -> Result<u128, RatingError> {
+        self.rating_by(BitCriteria::MOST_COMMON)
+    }
+
+    /// Keeps the numbers with the least common bit in each column (0 on
+    /// ties) until only one is left.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no number is left, which happens when all the
+    /// candidates have a 1 in a column, or more than one are left after the
+    /// last column.
+    pub fn co2_scrubber_rating(&self) -> Result<u128, RatingError> {
+        self.rating_by(BitCriteria::LEAST_COMMON)
+    }
+
+    /// Keeps the numbers with the bit `criteria` picks in each column until
+    /// only one is left.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's a tie `criteria` doesn't break, no number
+    /// is left, or more than one are left after the last column.
+    pub fn rating_by(&self, criteria: BitCriteria) -> Result<u128, RatingError> {
+        self.rating(criteria, |_| {})
+    }
+
+    // Each column only takes a binary search, and `step` gets to see it
+    pub(crate) fn rating<'a>(
+        &'a self,
+        criteria: BitCriteria,
+        mut step: impl FnMut(Step<'a>),
+    ) -> Result<u128, RatingError> {
+        let mut candidates = &self.sorted[..];
+        for column in 0..self.width {
+            if candidates.len() <= 1 {
+                break;
+            }
+            // The candidates all start with the same bits, so the ones with
+            // a 0 in this column come first
+            let bit = 1 << (self.width - 1 - column);
+            let zeros = candidates.partition_point(|&number| number & bit == 0);
+            let ones = candidates.len() - zeros;
+            let kept = criteria
+                .keep_ones(ones, zeros)
+                .ok_or(RatingError::Tie { column })?;
+            candidates = if kept {
+                &candidates[zeros..]
+            } else {
+                &candidates[..zeros]
+            };
+            step(Step {
+                column,
+                ones,
+                zeros,
+                kept: u8::from(kept),
+                survivors: candidates,
+            });
+            if candidates.is_empty() {
+                return Err(RatingError::NoneLeft { column });
+            }
+        }
+        match *candidates {
+            [] => Err(RatingError::Empty),
+            [number] => Ok(number),
+            [number, ..] => Err(RatingError::Duplicates {
+                number,
+                count: candidates.len(),
+            }),
+        }
+    }
+}
+
+// How many of `numbers` have a 1 in each column, counting all the columns at
+// once: bit `column` of `counters[i]` is bit `i` of that column's count.
+// They're flushed before they overflow.
+fn count_ones(width: usize, numbers: &[u128]) -> Vec<usize> {
+    const COUNTER_BITS: usize = 8;
+
+    let mut ones = vec![0; width];
+    for chunk in numbers.chunks((1 << COUNTER_BITS) - 1) {
+        let mut counters = [0_u128; COUNTER_BITS];
+        for &number in chunk {
+            let mut carry = number;
+            for counter in &mut counters {
+                (*counter, carry) = (*counter ^ carry, *counter & carry);
+            }
+        }
+        for (bit, counter) in counters.into_iter().enumerate() {
+            for (shift, ones) in ones.iter_mut().rev().enumerate() {
+                *ones += usize::from(counter >> shift & 1 == 1) << bit;
+            }
+        }
+    }
+    ones
+}
+
+// Sorts by the lowest `width` bits, a byte at a time, in O(n * width)
+fn radix_sort(width: usize, numbers: &[u128]) -> Vec<u128> {
+    let mut sorted = numbers.to_vec();
+    let mut buffer = vec![0; numbers.len()];
+    for shift in (0..width).step_by(8) {
+        let digit = |number: u128| (number >> shift) as usize & 0xff;
+        let mut starts = [0; 256];
+        for &number in &sorted {
+            starts[digit(number)] += 1;
+        }
+        let mut start = 0;
+        for count in &mut starts {
+            (*count, start) = (start, start + *count);
+        }
+        for &number in &sorted {
+            let start = &mut starts[digit(number)];
+            buffer[*start] = number;
+            *start += 1;
+        }
+        std::mem::swap(&mut sorted, &mut buffer);
+    }
+    sorted
+}
+
+// The lowest `width` bits
+fn mask(width: usize) -> u128 {
+    match width {
+        0 => 0,
+        _ => u128::MAX >> (MAX_WIDTH - width),
+    }
+}
+
+/// Why a rating can't be found. Columns count from 0.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RatingError {
+    /// There are no numbers at all.
+    Empty,
+    /// As many candidates have a 1 as a 0 in `column`, and that's an error.
+    Tie { column: usize },
+    /// None of the candidates have the bit the rating keeps in `column`.
+    NoneLeft { column: usize },
+    /// `count` numbers are left after the last column, all of them `number`.
+    Duplicates { number: u128, count: usize },
+}
+
+impl Display for RatingError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Empty => write!(formatter, "there are no numbers"),
+            Self::Tie { column } => {
+                write!(
+                    formatter,
+                    "as many numbers have a 1 as a 0 in column {column}"
+                )
+            }
+            Self::NoneLeft { column } => {
+                write!(formatter, "no number is left after column {column}")
+            }
+            Self::Duplicates { number, count } => write!(
+                formatter,
+                "{count} numbers are left after the last column, all of them {number}"
+            ),
+        }
+    }
+}
+
+impl Error for RatingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_rate, find_rating, generate, parse_input, NumbersError, TieBreak};
+    use aoc_core::Rng;
+
+    const SAMPLE_INPUT: &str = include_str!("sample-input.txt");
+
+    #[test]
+    fn test_diagnostics() {
+        let diagnostics = Diagnostics::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(diagnostics.width(), 5);
+        assert_eq!(diagnostics.numbers()[..3], [0b00100, 0b11110, 0b10110]);
+        assert_eq!(diagnostics.ones(), [7, 5, 8, 7, 5]);
+        assert_eq!(diagnostics.gamma_rate(), 22);
+        assert_eq!(diagnostics.epsilon_rate(), 9);
+        assert_eq!(diagnostics.oxygen_generator_rating(), Ok(23));
+        assert_eq!(diagnostics.co2_scrubber_rating(), Ok(10));
+    }
+
+    #[test]
+    fn test_wide_numbers() {
+        let ones = "1".repeat(MAX_WIDTH);
+        let input = format!("{ones}\n0{}\n", &ones[1..]);
+        let diagnostics = Diagnostics::parse(&input).unwrap();
+        assert_eq!(diagnostics.gamma_rate(), u128::MAX);
+        assert_eq!(diagnostics.epsilon_rate(), 0);
+        assert_eq!(diagnostics.co2_scrubber_rating(), Ok(u128::MAX >> 1));
+        assert!(Diagnostics::parse(&format!("{ones}0"))
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 1, column 1: expected a binary number of up to 128 bits"));
+        assert_eq!(Diagnostics::new(2, [0b111]).numbers(), [0b11]);
+    }
+
+    #[test]
+    fn test_rating_errors() {
+        let diagnostics = Diagnostics::parse("101\n100\n101\n011\n").unwrap();
+        assert_eq!(
+            diagnostics.oxygen_generator_rating(),
+            Err(RatingError::Duplicates {
+                number: 0b101,
+                count: 2
+            })
+        );
+        assert_eq!(diagnostics.co2_scrubber_rating(), Ok(0b011));
+        // Neither number has a 0 in column 0
+        let diagnostics = Diagnostics::parse("110\n111\n").unwrap();
+        assert_eq!(
+            diagnostics.co2_scrubber_rating(),
+            Err(RatingError::NoneLeft { column: 0 })
+        );
+        assert_eq!(
+            Diagnostics::parse("").unwrap().oxygen_generator_rating(),
+            Err(RatingError::Empty)
+        );
+    }
+
+    #[test]
+    fn test_radix_sort() {
+        let mut rng = Rng::new(21);
+        for width in [0, 1, 7, 8, 12, 64, 100, 128] {
+            let numbers: Vec<_> = (0..500)
+                .map(|_| {
+                    (u128::from(rng.next_u64()) << 64 | u128::from(rng.next_u64())) & mask(width)
+                })
+                .collect();
+            let mut expected = numbers.clone();
+            expected.sort_unstable();
+            assert_eq!(radix_sort(width, &numbers), expected);
+        }
+    }
+
+    #[test]
+    fn test_against_strings() {
+        let mut rng = Rng::new(20);
+        for size in [2, 3, 10, 100, 1_000] {
+            let input = generate(&mut rng, size);
+            let numbers = parse_input(&input).unwrap();
+            let diagnostics = Diagnostics::parse(&input).unwrap();
+            let width = diagnostics.width();
+            assert_eq!(
+                diagnostics.gamma_rate(),
+                find_rate(width, &numbers, BitCriteria::MOST_COMMON).unwrap() as u128
+            );
+            assert_eq!(
+                diagnostics.epsilon_rate(),
+                find_rate(width, &numbers, BitCriteria::LEAST_COMMON).unwrap() as u128
+            );
+            assert_eq!(
+                diagnostics.oxygen_generator_rating(),
+                Ok(find_rating(width, &numbers, BitCriteria::MOST_COMMON).unwrap() as u128)
+            );
+            assert_eq!(
+                diagnostics.co2_scrubber_rating(),
+                Ok(find_rating(width, &numbers, BitCriteria::LEAST_COMMON).unwrap() as u128)
+            );
+
+            for tie in [TieBreak::One, TieBreak::Zero, TieBreak::Error] {
+                for criteria in [BitCriteria::MostCommon(tie), BitCriteria::LeastCommon(tie)] {
+                    let expected = find_rate(width, &numbers, criteria)
+                        .map(|rate| rate as u128)
+                        .map_err(|error| match error {
+                            NumbersError::Tie { column } => RatingError::Tie { column },
+                            error => panic!("{error}"),
+                        });
+                    assert_eq!(diagnostics.rate_by(criteria), expected, "{criteria:?}");
+                    let expected = find_rating(width, &numbers, criteria)
+                        .map(|rating| rating as u128)
+                        .map_err(|error| match error {
+                            NumbersError::Tie { column } => RatingError::Tie { column },
+                            NumbersError::Rating(error) => error,
+                            error => panic!("{error}"),
+                        });
+                    assert_eq!(diagnostics.rating_by(criteria), expected, "{criteria:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{BitCriteria, Diagnostics, RatingError};
+use std::fmt::{Display, Formatter, Result as FormatResult};
+
+// How many of the numbers left after a column the table shows
+const SHOWN: usize = 4;
+
+/// Everything behind the answers for a diagnostic report, for explaining
+/// them. Columns count from 0, the most significant bit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiagnosticReport<'a> {
+    pub width: usize,
+    pub num_numbers: usize,
+    pub columns: Vec<BitCounts>,
+    pub gamma_rate: Result<u128, RatingError>,
+    pub epsilon_rate: Result<u128, RatingError>,
+    pub oxygen_generator: RatingTrace<'a>,
+    pub co2_scrubber: RatingTrace<'a>,
+}
+
+/// How many numbers have a 1 and a 0 in a column.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BitCounts {
+    pub ones: usize,
+    pub zeros: usize,
+}
+
+/// How a rating narrowed the numbers down, one column at a time, until one
+/// (or none) was left.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RatingTrace<'a> {
+    pub rating: Result<u128, RatingError>,
+    pub steps: Vec<Step<'a>>,
+}
+
+/// Of the numbers left before `column`, `ones` had a 1 and `zeros` a 0 in
+/// it, and the `survivors` are the ones with the `kept` bit, in ascending
+/// order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Step<'a> {
+    pub column: usize,
+    pub ones: usize,
+    pub zeros: usize,
+    pub kept: u8,
+    pub survivors: &'a [u128],
+}
+
+impl<'a> DiagnosticReport<'a> {
+    /// The report with the puzzle's bit criteria.
+    #[must_use]
+    pub fn new(diagnostics: &'a Diagnostics) -> Self {
+        Self::with_criteria(
+            diagnostics,
+            BitCriteria::MOST_COMMON,
+            BitCriteria::LEAST_COMMON,
+        )
+    }
+
+    /// The report with `most_common` picking the bits of the gamma rate and
+    /// oxygen generator rating, and `least_common` those of the epsilon rate
+    /// and CO2 scrubber rating.
+    #[must_use]
+    pub fn with_criteria(
+        diagnostics: &'a Diagnostics,
+        most_common: BitCriteria,
+        least_common: BitCriteria,
+    ) -> Self {
+        let num_numbers = diagnostics.numbers().len();
+        let trace = |criteria| {
+            let mut steps = Vec::new();
+            let rating = diagnostics.rating(criteria, |step| steps.push(step));
+            RatingTrace { rating, steps }
+        };
+        Self {
+            width: diagnostics.width(),
+            num_numbers,
+            columns: diagnostics
+                .ones()
+                .iter()
+                .map(|&ones| BitCounts {
+                    ones,
+                    zeros: num_numbers - ones,
+                })
+                .collect(),
+            gamma_rate: diagnostics.rate_by(most_common),
+            epsilon_rate: diagnostics.rate_by(least_common),
+            oxygen_generator: trace(most_common),
+            co2_scrubber: trace(least_common),
+        }
+    }
+}
+
+impl Display for DiagnosticReport<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        let binary = |number: u128| format!("{number:0width$b}", width = self.width);
+        // Wide enough for any count, and the headings
+        let count = self.num_numbers.to_string().len().max("Zeros".len());
+
+        writeln!(
+            formatter,
+            "{} numbers of {} bits",
+            self.num_numbers, self.width
+        )?;
+        writeln!(formatter)?;
+        writeln!(formatter, "Column  {:>count$}  {:>count$}", "Ones", "Zeros")?;
+        for (column, BitCounts { ones, zeros }) in self.columns.iter().enumerate() {
+            writeln!(formatter, "{column:>6}  {ones:>count$}  {zeros:>count$}")?;
+        }
+        writeln!(formatter)?;
+        for (name, rate) in [
+            ("Gamma rate", self.gamma_rate),
+            ("Epsilon rate", self.epsilon_rate),
+        ] {
+            match rate {
+                Ok(rate) => writeln!(formatter, "{name}: {} ({rate})", binary(rate))?,
+                Err(error) => writeln!(formatter, "{name}: none, {error}")?,
+            }
+        }
+        writeln!(
+            formatter,
+            "Power consumption: {}",
+            product(self.gamma_rate, self.epsilon_rate)
+        )?;
+
+        for (name, trace) in [
+            ("Oxygen generator", &self.oxygen_generator),
+            ("CO2 scrubber", &self.co2_scrubber),
+        ] {
+            writeln!(formatter)?;
+            match trace.rating {
+                Ok(rating) => writeln!(formatter, "{name} rating: {} ({rating})", binary(rating))?,
+                Err(error) => writeln!(formatter, "{name} rating: none, {error}")?,
+            }
+            writeln!(
+                formatter,
+                "Column  {:>count$}  {:>count$}  Kept  {:>count$}",
+                "Ones", "Zeros", "Left"
+            )?;
+            for step in &trace.steps {
+                write!(
+                    formatter,
+                    "{:>6}  {:>count$}  {:>count$}  {:>4}  {:>count$}",
+                    step.column,
+                    step.ones,
+                    step.zeros,
+                    step.kept,
+                    step.survivors.len()
+                )?;
+                for &number in step.survivors.iter().take(SHOWN) {
+                    write!(formatter, "  {}", binary(number))?;
+                }
+                if step.survivors.len() > SHOWN {
+                    write!(formatter, "  ...")?;
+                }
+                writeln!(formatter)?;
+            }
+        }
+        writeln!(formatter)?;
+        writeln!(
+            formatter,
+            "Life support rating: {}",
+            product(self.oxygen_generator.rating, self.co2_scrubber.rating)
+        )
+    }
+}
+
+fn product(a: Result<u128, RatingError>, b: Result<u128, RatingError>) -> String {
+    match (a, b) {
+        (Ok(a), Ok(b)) => a
+            .checked_mul(b)
+            .map_or_else(|| "too large".to_owned(), |product| product.to_string()),
+        _ => "none".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TieBreak;
+
+    const SAMPLE_INPUT: &str = include_str!("sample-input.txt");
+
+    #[test]
+    fn test_report() {
+        let diagnostics = Diagnostics::parse(SAMPLE_INPUT).unwrap();
+        let report = DiagnosticReport::new(&diagnostics);
+        assert_eq!(report.columns[0], BitCounts { ones: 7, zeros: 5 });
+        assert_eq!((report.gamma_rate, report.epsilon_rate), (Ok(22), Ok(9)));
+        assert_eq!(report.oxygen_generator.rating, Ok(23));
+        assert_eq!(report.oxygen_generator.steps.len(), 5);
+        assert_eq!(
+            report.co2_scrubber.steps[1],
+            Step {
+                column: 1,
+                ones: 2,
+                zeros: 3,
+                kept: 1,
+                survivors: &[0b01010, 0b01111],
+            }
+        );
+        assert_eq!(
+            report.to_string(),
+            "12 numbers of 5 bits
+
+Column   Ones  Zeros
+     0      7      5
+     1      5      7
+     2      8      4
+     3      7      5
+     4      5      7
+
+Gamma rate: 10110 (22)
+Epsilon rate: 01001 (9)
+Power consumption: 198
+
+Oxygen generator rating: 10111 (23)
+Column   Ones  Zeros  Kept   Left
+     0      7      5     1      7  10000  10101  10110  10111  ...
+     1      3      4     0      4  10000  10101  10110  10111
+     2      3      1     1      3  10101  10110  10111
+     3      2      1     1      2  10110  10111
+     4      1      1     1      1  10111
+
+CO2 scrubber rating: 01010 (10)
+Column   Ones  Zeros  Kept   Left
+     0      7      5     0      5  00010  00100  00111  01010  ...
+     1      2      3     1      2  01010  01111
+     2      1      1     0      1  01010
+
+Life support rating: 230
+"
+        );
+    }
+
+    #[test]
+    fn test_report_without_ratings() {
+        let diagnostics = Diagnostics::parse("11\n11\n").unwrap();
+        let report = DiagnosticReport::new(&diagnostics);
+        let text = report.to_string();
+        assert!(text.contains(
+            "Oxygen generator rating: none, 2 numbers are left after the last column, all of them 3"
+        ));
+        assert!(text.contains("CO2 scrubber rating: none, no number is left after column 0"));
+        assert!(text.ends_with("Life support rating: none\n"));
+        assert_eq!(report.co2_scrubber.steps[0].survivors, []);
+
+        let diagnostics = Diagnostics::parse("10\n01\n").unwrap();
+        let no_ties = BitCriteria::MostCommon(TieBreak::Error);
+        let report = DiagnosticReport::with_criteria(&diagnostics, no_ties, no_ties);
+        assert_eq!(report.epsilon_rate, Err(RatingError::Tie { column: 0 }));
+        let text = report.to_string();
+        assert!(text.contains("Gamma rate: none, as many numbers have a 1 as a 0 in column 0"));
+        assert!(text.contains("Power consumption: none"));
+        assert!(text.contains("CO2 scrubber rating: none, as many numbers"));
+
+        let diagnostics = Diagnostics::default();
+        assert_eq!(
+            DiagnosticReport::new(&diagnostics).oxygen_generator,
+            RatingTrace {
+                rating: Err(RatingError::Empty),
+                steps: vec![],
+            }
+        );
+    }
+}
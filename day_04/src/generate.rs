@@ -0,0 +1,61 @@
+use aoc_core::Rng;
+use std::fmt::Write;
+
+// Like the puzzle input, every number from 0 to 99 is drawn once
+const NUM_NUMBERS: usize = 100;
+
+/// A game of bingo with `size` boards (but at least one), and one board that
+/// clearly wins last.
+///
+/// The last board to win has a number on its diagonal that is on no other
+/// board and drawn at the very end, so every row and column on it needs
+/// one of those to be complete.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut numbers: Vec<usize> = (0..NUM_NUMBERS).collect();
+    rng.shuffle(&mut numbers);
+    let (shared, last) = numbers.split_at(NUM_NUMBERS - 5);
+
+    let mut boards: Vec<Vec<usize>> = (1..size)
+        .map(|_| {
+            let mut board = shared.to_vec();
+            rng.shuffle(&mut board);
+            board.truncate(25);
+            board
+        })
+        .collect();
+    let mut last_board = shared.to_vec();
+    rng.shuffle(&mut last_board);
+    last_board.truncate(20);
+    for (index, &number) in last.iter().enumerate() {
+        last_board.insert(6 * index, number);
+    }
+    boards.insert(rng.below(size), last_board);
+
+    let numbers: Vec<_> = numbers.iter().map(usize::to_string).collect();
+    let mut input = numbers.join(",");
+    input.push('\n');
+    for board in boards {
+        input.push('\n');
+        for row in board.chunks(5) {
+            let row: Vec<_> = row.iter().map(|number| format!("{number:>2}")).collect();
+            writeln!(input, "{}", row.join(" ")).expect("writing to a String can't fail");
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day04;
+    use aoc_core::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(Day04::parse(&input).unwrap().1.len(), 100);
+        let input = generate(&mut Rng::new(1), 0);
+        assert_eq!(Day04::parse(&input).unwrap().1.len(), 1);
+    }
+}
@@ -1,15 +1,62 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
 
-use std::str::FromStr;
+mod generate;
+mod rules;
 
-fn part_1(input: &str) -> Result<usize, BingoError> {
-    let (numbers, mut boards) = parse_input(input)?;
+use aoc_core::{parse_token, Answer, ParseError, Solution};
+pub use generate::generate;
+pub use rules::{Mask, Rule, WinRule};
 
-    for number in numbers {
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+    str::FromStr,
+};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = (Vec<usize>, Vec<BingoBoard>);
+    type Error = BingoError;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+fn part_1((numbers, boards): &(Vec<usize>, Vec<BingoBoard>)) -> Result<usize, BingoError> {
+    first_winner(numbers, boards, &Rule::Line)
+}
+
+fn part_2((numbers, boards): &(Vec<usize>, Vec<BingoBoard>)) -> Result<usize, BingoError> {
+    last_winner(numbers, boards, &Rule::Line)
+}
+
+/// The score of the first board to win by `rule`.
+///
+/// # Errors
+///
+/// Returns an error if `rule` doesn't fit a board, or no board wins.
+pub fn first_winner(
+    numbers: &[usize],
+    boards: &[BingoBoard],
+    rule: &impl WinRule,
+) -> Result<usize, BingoError> {
+    check_fit(boards, rule)?;
+    let mut boards = boards.to_vec();
+
+    for &number in numbers {
         for board in &mut boards {
-            board.mark(number);
+            board.mark(number, rule);
             if board.winner {
                 return Ok(board.score(number));
             }
@@ -19,19 +66,30 @@ fn part_1(input: &str) -> Result<usize, BingoError> {
     Err(BingoError::NoWinningBoard)
 }
 
-fn part_2(input: &str) -> Result<usize, BingoError> {
-    let (numbers, mut boards) = parse_input(input)?;
+/// The score of the last board to win by `rule`.
+///
+/// # Errors
+///
+/// Returns an error if `rule` doesn't fit a board, or no board wins last on
+/// its own.
+pub fn last_winner(
+    numbers: &[usize],
+    boards: &[BingoBoard],
+    rule: &impl WinRule,
+) -> Result<usize, BingoError> {
+    check_fit(boards, rule)?;
+    let mut boards = boards.to_vec();
 
-    for number in numbers {
+    for &number in numbers {
         if boards.len() > 1 {
             for board in &mut boards {
-                board.mark(number);
+                board.mark(number, rule);
             }
-            boards = boards.into_iter().filter(|b| !b.winner).collect();
-        } else {
-            boards[0].mark(number);
-            if boards[0].winner {
-                return Ok(boards[0].score(number));
+            boards.retain(|b| !b.winner);
+        } else if let Some(board) = boards.first_mut() {
+            board.mark(number, rule);
+            if board.winner {
+                return Ok(board.score(number));
             }
         }
     }
@@ -39,40 +97,99 @@ fn part_2(input: &str) -> Result<usize, BingoError> {
     Err(BingoError::NoWinningBoard)
 }
 
+fn check_fit(boards: &[BingoBoard], rule: &impl WinRule) -> Result<(), BingoError> {
+    match boards
+        .iter()
+        .map(BingoBoard::shape)
+        .enumerate()
+        .find(|&(_, shape)| !rule.fits(shape))
+    {
+        Some((board, shape)) => Err(BingoError::RuleDoesNotFit { board, shape }),
+        None => Ok(()),
+    }
+}
+
 fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<BingoBoard>), BingoError> {
     let mut parts = input.split("\n\n");
     let numbers: Vec<usize> = parts
         .next()
-        .ok_or(BingoError::InvalidInput)?
+        .unwrap_or_default()
         .split(',')
-        .map(|n| n.parse().map_err(|_| BingoError::InvalidInput))
+        .map(|n| parse_token(input, n, "a number"))
         .collect::<Result<_, _>>()?;
-    let boards: Vec<BingoBoard> = parts.map(str::parse).collect::<Result<_, _>>()?;
+    let boards: Vec<BingoBoard> = parts
+        .enumerate()
+        .map(|(index, board)| {
+            board
+                .parse()
+                .map_err(|error: ParseError| BingoError::InvalidBoard {
+                    board: index,
+                    error: error.within(input, board),
+                })
+        })
+        .collect::<Result<_, _>>()?;
+
+    // Every board is shaped like the first one
+    if let Some(expected) = boards.first().map(BingoBoard::shape) {
+        if let Some((board, shape)) = boards
+            .iter()
+            .map(BingoBoard::shape)
+            .enumerate()
+            .find(|&(_, shape)| shape != expected)
+        {
+            return Err(BingoError::MismatchedBoard {
+                board,
+                shape,
+                expected,
+            });
+        }
+    }
     Ok((numbers, boards))
 }
 
-struct BingoBoard {
-    numbers: [BingoNumber; 25],
+/// The numbers on a board, row by row.
+#[derive(Clone, Debug)]
+pub struct BingoBoard {
+    rows: usize,
+    columns: usize,
+    numbers: Vec<BingoNumber>,
     winner: bool,
 }
 
+/// How many rows and columns a board has.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Shape {
+    pub rows: usize,
+    pub columns: usize,
+}
+
+impl Display for Shape {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(formatter, "{}x{}", self.rows, self.columns)
+    }
+}
+
 impl BingoBoard {
-    fn mark(&mut self, number: usize) {
-        if let Some(index) = self.numbers.iter().position(|&n| n == number) {
-            self.numbers[index] = BingoNumber::Marked;
-            self.check_win(index);
+    #[must_use]
+    pub fn shape(&self) -> Shape {
+        Shape {
+            rows: self.rows,
+            columns: self.columns,
         }
     }
 
-    fn check_win(&mut self, index: usize) {
-        let row = index / 5;
-        let column = index % 5;
-
-        let mut row_numbers = self.numbers[row * 5..row * 5 + 5].iter();
-        let mut column_numbers = (0..5).map(|row| &self.numbers[5 * row + column]);
+    /// Whether the number in `row` and `column` has been drawn.
+    #[must_use]
+    pub fn is_marked(&self, row: usize, column: usize) -> bool {
+        self.numbers[row * self.columns + column].is_marked()
+    }
 
-        self.winner =
-            row_numbers.all(BingoNumber::is_marked) || column_numbers.all(BingoNumber::is_marked);
+    fn mark(&mut self, number: usize, rule: &impl WinRule) {
+        if let Some(index) = self.numbers.iter().position(|&n| n == number) {
+            self.numbers[index] = BingoNumber::Marked;
+            self.winner =
+                self.winner || rule.wins(self, index / self.columns, index % self.columns);
+        }
     }
 
     fn score(&self, last_number: usize) -> usize {
@@ -82,27 +199,49 @@ impl BingoBoard {
 }
 
 impl FromStr for BingoBoard {
-    type Err = BingoError;
+    type Err = ParseError;
 
+    /// Takes the shape from the numbers: a row per line, and as many
+    /// columns as the first row has numbers.
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut numbers_iter = input
-            .split(|c| c == '\n' || c == ' ')
-            .filter(|s| !s.is_empty())
-            .map(BingoNumber::from_str);
-        let mut numbers = [BingoNumber::Unmarked(0); 25];
-        for number in &mut numbers {
-            *number = numbers_iter
-                .next()
-                .unwrap_or(Err(BingoError::InvalidInput))?;
+        let mut numbers = Vec::new();
+        let mut rows = 0;
+        let mut columns = 0;
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let start = numbers.len();
+            for number in line.split(' ').filter(|s| !s.is_empty()) {
+                numbers.push(parse_token(input, number, "a number").map(BingoNumber::Unmarked)?);
+            }
+            let length = numbers.len() - start;
+            if rows == 0 {
+                columns = length;
+            } else if length != columns {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("a row of {columns} numbers, like the first one"),
+                ));
+            }
+            rows += 1;
+        }
+        if rows == 0 {
+            let end = input.trim_end().len();
+            return Err(ParseError::at(
+                input,
+                &input[end..end],
+                "a board of numbers",
+            ));
         }
         Ok(Self {
+            rows,
+            columns,
             numbers,
             winner: false,
         })
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 enum BingoNumber {
     Unmarked(usize),
     Marked,
@@ -117,17 +256,6 @@ impl BingoNumber {
     }
 }
 
-impl FromStr for BingoNumber {
-    type Err = BingoError;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        input
-            .parse::<usize>()
-            .map(BingoNumber::Unmarked)
-            .map_err(|_| BingoError::InvalidInput)
-    }
-}
-
 impl From<&BingoNumber> for Option<usize> {
     fn from(&bingo_number: &BingoNumber) -> Self {
         match bingo_number {
@@ -146,38 +274,125 @@ impl PartialEq<usize> for BingoNumber {
     }
 }
 
+/// Boards count from 0.
 #[derive(Debug)]
-enum BingoError {
-    InvalidInput,
+pub enum BingoError {
+    InvalidInput(ParseError),
+    InvalidBoard {
+        board: usize,
+        error: ParseError,
+    },
+    /// The board isn't shaped like the first one.
+    MismatchedBoard {
+        board: usize,
+        shape: Shape,
+        expected: Shape,
+    },
+    /// The win rule can't be played on the board.
+    RuleDoesNotFit {
+        board: usize,
+        shape: Shape,
+    },
     NoWinningBoard,
 }
 
+impl From<ParseError> for BingoError {
+    fn from(error: ParseError) -> Self {
+        Self::InvalidInput(error)
+    }
+}
+
+impl Display for BingoError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::InvalidInput(error) => write!(formatter, "{error}"),
+            Self::InvalidBoard { board, error } => write!(formatter, "board {board}: {error}"),
+            Self::MismatchedBoard {
+                board,
+                shape,
+                expected,
+            } => write!(
+                formatter,
+                "board {board} is {shape}, but board 0 is {expected}"
+            ),
+            Self::RuleDoesNotFit { board, shape } => write!(
+                formatter,
+                "the win rule can't be played on board {board}, which is {shape}"
+            ),
+            Self::NoWinningBoard => write!(formatter, "no board wins"),
+        }
+    }
+}
+
+impl Error for BingoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidInput(error) | Self::InvalidBoard { error, .. } => Some(error),
+            Self::MismatchedBoard { .. } | Self::RuleDoesNotFit { .. } | Self::NoWinningBoard => {
+                None
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test() {
-        assert_eq!(part_1(SAMPLE_INPUT).unwrap(), 4_512);
-        assert_eq!(part_1(INPUT).unwrap(), 2_496);
+        assert_eq!(part_1(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(), 4_512);
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()).unwrap(), 2_496);
 
-        assert_eq!(part_2(SAMPLE_INPUT).unwrap(), 1_924);
-        assert_eq!(part_2(INPUT).unwrap(), 25_925);
+        assert_eq!(part_2(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(), 1_924);
+        assert_eq!(part_2(&parse_input(INPUT).unwrap()).unwrap(), 25_925);
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT));
+    #[test]
+    fn test_shapes() {
+        // The first board wins with its first row, the second one with its
+        // last column
+        let input = parse_input("1,2,3,4\n\n1 2 3\n4 5 6\n\n7 4 1\n8 5 2\n").unwrap();
+        assert_eq!(
+            input.1[0].shape(),
+            Shape {
+                rows: 2,
+                columns: 3
+            }
+        );
+        assert_eq!(part_1(&input).unwrap(), (7 + 4 + 8 + 5) * 2);
+        assert_eq!(part_2(&input).unwrap(), (4 + 5 + 6) * 3);
+
+        // Every row is a single number, so the first one drawn wins
+        let input = parse_input("3,1,2\n\n1\n2\n3\n").unwrap();
+        assert_eq!(part_1(&input).unwrap(), (1 + 2) * 3);
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT));
+    #[test]
+    fn test_parse_errors() {
+        let parse_error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            parse_error("1,2\n\n1 2\n3 4\n\n1 2\n3\n"),
+            "board 1: line 7, column 1: expected a row of 2 numbers, like the first one, found `3`"
+        );
+        assert_eq!(
+            parse_error("1,2\n\n1 2\n3 4\n\n1 x\n"),
+            "board 1: line 6, column 3: expected a number, found `x`"
+        );
+        assert_eq!(
+            parse_error("1,2\n\n1 2\n3 4\n\n1 2\n3 4\n\n1 2 3\n4 5 6\n"),
+            "board 2 is 2x3, but board 0 is 2x2"
+        );
+        assert_eq!(
+            parse_error("1,2\n\n1 2\n3 4\n\n1 2\n"),
+            "board 1 is 1x2, but board 0 is 2x2"
+        );
+        assert_eq!(
+            parse_error("1,x\n\n1 2\n"),
+            "line 1, column 3: expected a number, found `x`"
+        );
     }
 }
@@ -0,0 +1,256 @@
+use crate::{BingoBoard, Shape};
+use aoc_core::ParseError;
+use std::str::FromStr;
+
+/// When a board wins, checked every time one of its numbers is drawn.
+///
+/// Any `Fn(&BingoBoard, usize, usize) -> bool` is a rule, so house rules
+/// don't need a type of their own.
+pub trait WinRule {
+    /// Whether `board` wins now that the number in `row` and `column` is
+    /// marked.
+    fn wins(&self, board: &BingoBoard, row: usize, column: usize) -> bool;
+
+    /// Whether boards of `shape` can be played with this rule at all.
+    fn fits(&self, _shape: Shape) -> bool {
+        true
+    }
+}
+
+impl<F> WinRule for F
+where
+    F: Fn(&BingoBoard, usize, usize) -> bool,
+{
+    fn wins(&self, board: &BingoBoard, row: usize, column: usize) -> bool {
+        self(board, row, column)
+    }
+}
+
+/// The usual ways to win.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Rule {
+    /// A complete row or column, like the puzzle.
+    Line,
+    /// A complete diagonal from corner to corner, on square boards only.
+    Diagonal,
+    /// All four corners.
+    Corners,
+    /// Every number on the board.
+    Blackout,
+    /// Every number the mask covers, on boards of the same shape.
+    Pattern(Mask),
+    /// Whichever of these comes first.
+    AnyOf(Vec<Rule>),
+}
+
+impl WinRule for Rule {
+    fn wins(&self, board: &BingoBoard, row: usize, column: usize) -> bool {
+        let Shape { rows, columns } = board.shape();
+        // Boards that already won aren't checked again, so only what the number
+        // just marked can complete matters
+        match self {
+            Self::Line => {
+                (0..columns).all(|column| board.is_marked(row, column))
+                    || (0..rows).all(|row| board.is_marked(row, column))
+            }
+            Self::Diagonal => {
+                (row == column && (0..rows).all(|index| board.is_marked(index, index)))
+                    || (row + column == columns - 1
+                        && (0..rows).all(|index| board.is_marked(index, columns - 1 - index)))
+            }
+            Self::Corners => [
+                (0, 0),
+                (0, columns - 1),
+                (rows - 1, 0),
+                (rows - 1, columns - 1),
+            ]
+            .into_iter()
+            .all(|(row, column)| board.is_marked(row, column)),
+            Self::Blackout => {
+                (0..rows).all(|row| (0..columns).all(|column| board.is_marked(row, column)))
+            }
+            Self::Pattern(mask) => {
+                mask.covers(row, column)
+                    && (0..rows).all(|row| {
+                        (0..columns)
+                            .all(|column| !mask.covers(row, column) || board.is_marked(row, column))
+                    })
+            }
+            Self::AnyOf(rules) => rules.iter().any(|rule| rule.wins(board, row, column)),
+        }
+    }
+
+    fn fits(&self, shape: Shape) -> bool {
+        match self {
+            Self::Line | Self::Corners | Self::Blackout => true,
+            Self::Diagonal => shape.rows == shape.columns,
+            Self::Pattern(mask) => mask.shape() == shape,
+            Self::AnyOf(rules) => rules.iter().all(|rule| rule.fits(shape)),
+        }
+    }
+}
+
+/// Which numbers on a board make up a pattern, written a row per line with
+/// `X` for the numbers it covers and `.` for the rest:
+///
+/// ```text
+/// X...X
+/// .X.X.
+/// ..X..
+/// .X.X.
+/// X...X
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mask {
+    columns: usize,
+    // Row by row
+    cells: Vec<bool>,
+}
+
+impl Mask {
+    #[must_use]
+    pub fn shape(&self) -> Shape {
+        Shape {
+            rows: self.cells.len() / self.columns,
+            columns: self.columns,
+        }
+    }
+
+    #[must_use]
+    pub fn covers(&self, row: usize, column: usize) -> bool {
+        self.cells[row * self.columns + column]
+    }
+}
+
+impl FromStr for Mask {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::new();
+        let mut columns = 0;
+        let mut first_row = None;
+        for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if !line.bytes().all(|byte| matches!(byte, b'X' | b'.')) {
+                return Err(ParseError::at(input, line, "a row of `X`s and `.`s"));
+            }
+            if cells.is_empty() {
+                columns = line.len();
+                first_row = Some(line);
+            } else if line.len() != columns {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("a row of {columns} `X`s and `.`s, like the first one"),
+                ));
+            }
+            cells.extend(line.bytes().map(|byte| byte == b'X'));
+        }
+        let Some(first_row) = first_row else {
+            let end = input.trim_end().len();
+            return Err(ParseError::at(input, &input[end..end], "a pattern"));
+        };
+        // A pattern that covers nothing could never win
+        if !cells.contains(&true) {
+            return Err(ParseError::at(
+                input,
+                first_row,
+                "a pattern with at least one `X`",
+            ));
+        }
+        Ok(Self { columns, cells })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{first_winner, last_winner, BingoError};
+
+    const BOARD: &str = "1 2 3\n4 5 6\n7 8 9\n";
+
+    // The score of the only board, if it wins
+    fn play(numbers: &[usize], rule: &impl WinRule) -> Option<usize> {
+        first_winner(numbers, &[BOARD.parse().unwrap()], rule).ok()
+    }
+
+    #[test]
+    fn test_rules() {
+        assert_eq!(play(&[1, 5, 9, 3, 7], &Rule::Line), None);
+        assert_eq!(
+            play(&[1, 5, 9, 3, 7, 2], &Rule::Line),
+            Some((4 + 6 + 8) * 2)
+        );
+        assert_eq!(play(&[1, 5, 9], &Rule::Diagonal), Some(30 * 9));
+        assert_eq!(play(&[3, 5, 7], &Rule::Diagonal), Some(30 * 7));
+        assert_eq!(play(&[1, 2, 3], &Rule::Diagonal), None);
+        assert_eq!(
+            play(&[1, 3, 7, 9], &Rule::Corners),
+            Some((2 + 4 + 5 + 6 + 8) * 9)
+        );
+        assert_eq!(play(&[1, 2, 3, 4, 5, 6, 7, 8], &Rule::Blackout), None);
+        assert_eq!(play(&[9, 8, 7, 6, 5, 4, 3, 2, 1], &Rule::Blackout), Some(0));
+
+        let plus = Rule::Pattern(".X.\nXXX\n.X.\n".parse().unwrap());
+        assert_eq!(play(&[1, 2, 4, 5, 6], &plus), None);
+        assert_eq!(play(&[1, 2, 4, 5, 6, 8], &plus), Some((3 + 7 + 9) * 8));
+        let either = Rule::AnyOf(vec![Rule::Diagonal, Rule::Corners]);
+        assert_eq!(play(&[1, 3, 7, 5], &either), Some((2 + 4 + 6 + 8 + 9) * 5));
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        // Anything in the middle row wins
+        let middle = |board: &BingoBoard, row, _| row == board.shape().rows / 2;
+        assert_eq!(play(&[1, 4], &middle), Some((45 - 1 - 4) * 4));
+
+        let boards = ["1 2\n3 4\n".parse().unwrap(), "5 6\n7 8\n".parse().unwrap()];
+        assert_eq!(
+            last_winner(&[1, 5, 8, 4], &boards, &Rule::Diagonal).unwrap(),
+            (2 + 3) * 4
+        );
+    }
+
+    #[test]
+    fn test_fits() {
+        let board: BingoBoard = "1 2 3\n4 5 6\n".parse().unwrap();
+        let error = first_winner(&[1], &[board], &Rule::Diagonal).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the win rule can't be played on board 0, which is 2x3"
+        );
+        let square = Rule::Pattern("XX\nXX\n".parse().unwrap());
+        assert!(square.fits(Shape {
+            rows: 2,
+            columns: 2
+        }));
+        assert!(!Rule::AnyOf(vec![Rule::Line, square]).fits(Shape {
+            rows: 3,
+            columns: 3
+        }));
+        assert!(matches!(
+            first_winner(&[1], &[BOARD.parse().unwrap()], &Rule::Blackout),
+            Err(BingoError::NoWinningBoard)
+        ));
+    }
+
+    #[test]
+    fn test_parse_mask() {
+        let parse_error = |input: &str| input.parse::<Mask>().unwrap_err().to_string();
+        assert_eq!(
+            parse_error("X.\nX\n"),
+            "line 2, column 1: expected a row of 2 `X`s and `.`s, like the first one, found `X`"
+        );
+        assert_eq!(
+            parse_error("X.\nXo\n"),
+            "line 2, column 1: expected a row of `X`s and `.`s, found `Xo`"
+        );
+        assert_eq!(
+            parse_error("\n"),
+            "line 1, column 1: expected a pattern, found nothing"
+        );
+        assert_eq!(
+            parse_error("\n  ...\n  ...\n"),
+            "line 2, column 3: expected a pattern with at least one `X`, found `...`"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use aoc_core::Rng;
+use std::fmt::Write;
+
+const MAX_COORDINATE: usize = 999;
+
+/// `size` horizontal, vertical and diagonal lines of hydrothermal vents, with
+/// coordinates from 0 to 999.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::with_capacity(20 * size);
+    for _ in 0..size {
+        let a = rng.range(0..=MAX_COORDINATE);
+        let b = rng.range(0..=MAX_COORDINATE);
+        let c = rng.range(0..=MAX_COORDINATE);
+        let ((x1, y1), (x2, y2)) = match rng.below(3) {
+            0 => ((a, c), (b, c)),
+            1 => ((c, a), (c, b)),
+            _ => {
+                // Diagonal lines have to stay inside the area too
+                let length = a.abs_diff(b);
+                if rng.chance(1, 2) {
+                    let y1 = rng.range(0..=MAX_COORDINATE - length);
+                    ((a, y1), (b, y1 + length))
+                } else {
+                    let y1 = rng.range(length..=MAX_COORDINATE);
+                    ((a, y1), (b, y1 - length))
+                }
+            }
+        };
+        writeln!(input, "{x1},{y1} -> {x2},{y2}").expect("writing to a String can't fail");
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day05;
+    use aoc_core::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 500);
+        assert_eq!(Day05::parse(&input).unwrap().len(), 500);
+    }
+}
@@ -1,6 +1,11 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(int_abs_diff, test)]
+
+mod generate;
+
+use aoc_core::{parse_lines, parse_token, Answer, ParseError, Solution};
+use aoc_grid::Grid;
+pub use generate::generate;
 
 use std::{
     cmp::{
@@ -8,84 +13,74 @@ use std::{
         Ordering::{Equal, Greater, Less},
     },
     iter::successors,
-    num::ParseIntError,
-    ops::{Index, IndexMut},
     str::FromStr,
 };
 
-fn part_1<const N: usize>(input: &str) -> Result<usize, InvalidInputError> {
-    let lines = parse_input::<N>(input)?;
-    let mut grid = Grid::new();
+// Vents are counted in a grid covering every line, so coordinates can't be
+// arbitrarily large
+pub const MAX_COORDINATE: usize = 4_095;
 
-    for coordinate in lines
-        .iter()
-        .filter(|line| !line.is_diagonal())
-        .flat_map(Line::coordinates)
-    {
-        grid[coordinate] += 1;
-    }
+pub struct Day05;
 
-    Ok(grid.num_overlaps())
-}
-
-fn part_2<const N: usize>(input: &str) -> Result<usize, InvalidInputError> {
-    let lines = parse_input::<N>(input)?;
-    let mut grid = Grid::new();
+impl Solution for Day05 {
+    type Input<'a> = Vec<Line>;
+    type Error = ParseError;
 
-    for coordinate in lines.iter().flat_map(Line::coordinates) {
-        grid[coordinate] += 1;
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
     }
 
-    Ok(grid.num_overlaps())
-}
-
-fn parse_input<const N: usize>(input: &str) -> Result<[Line; N], InvalidInputError> {
-    let mut input_lines = input.lines();
-    let mut lines = [Line::default(); N];
-
-    for line in &mut lines {
-        *line = input_lines.next().ok_or(InvalidInputError)?.parse()?;
+    fn part_1(lines: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(lines).into())
     }
 
-    Ok(lines)
+    fn part_2(lines: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(lines).into())
+    }
 }
 
-struct Grid([[u8; Self::NUM_COLUMS]; Self::NUM_ROWS]);
-
-impl Grid {
-    const NUM_ROWS: usize = 1_000;
-    const NUM_COLUMS: usize = 1_000;
-
-    fn new() -> Self {
-        Self([[0; Self::NUM_COLUMS]; Self::NUM_ROWS])
-    }
+fn part_1(lines: &[Line]) -> usize {
+    count_overlaps(
+        lines.iter().filter(|line| !line.is_diagonal()),
+        covering_grid(lines),
+    )
+}
 
-    fn num_overlaps(&self) -> usize {
-        self.0.iter().flatten().filter(|&&x| x > 1).count()
-    }
+fn part_2(lines: &[Line]) -> usize {
+    count_overlaps(lines.iter(), covering_grid(lines))
 }
 
-impl Index<Coordinate> for Grid {
-    type Output = u8;
+fn parse_input(input: &str) -> Result<Vec<Line>, ParseError> {
+    parse_lines(input, str::parse)
+}
 
-    fn index(&self, Coordinate(x, y): Coordinate) -> &Self::Output {
-        &self.0[y][x]
-    }
+// Just large enough for every coordinate on the lines
+fn covering_grid(lines: &[Line]) -> Grid<u8> {
+    let Coordinate(max_x, max_y) = lines
+        .iter()
+        .flat_map(|&Line(start, end)| [start, end])
+        .fold(
+            Coordinate(0, 0),
+            |Coordinate(max_x, max_y), Coordinate(x, y)| Coordinate(max(max_x, x), max(max_y, y)),
+        );
+    Grid::new(max_y + 1, max_x + 1, 0)
 }
 
-impl IndexMut<Coordinate> for Grid {
-    fn index_mut(&mut self, Coordinate(x, y): Coordinate) -> &mut Self::Output {
-        &mut self.0[y][x]
+fn count_overlaps<'a>(lines: impl Iterator<Item = &'a Line>, mut grid: Grid<u8>) -> usize {
+    for Coordinate(x, y) in lines.flat_map(Line::coordinates) {
+        // Saturates, since only whether there are two or more lines matters
+        grid[(y, x)] = grid[(y, x)].saturating_add(1);
     }
+    grid.iter().filter(|&&count| count > 1).count()
 }
 
 #[derive(Clone, Copy, Debug, Default)]
-struct Line(Coordinate, Coordinate);
+pub struct Line(Coordinate, Coordinate);
 
 impl Line {
     fn is_diagonal(&self) -> bool {
         let Self(Coordinate(x1, y1), Coordinate(x2, y2)) = self;
-        // Assumes lines can only be horizontal, vertical, or diagonal
+        // Parsing made sure lines are horizontal, vertical, or diagonal
         x1 != x2 && y1 != y2
     }
 
@@ -101,11 +96,26 @@ impl Line {
 }
 
 impl FromStr for Line {
-    type Err = InvalidInputError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_once(" -> ").ok_or(InvalidInputError)?;
-        Ok(Self(start.parse()?, end.parse()?))
+        let (start, end) = s
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::at(s, s, "a line like `0,9 -> 5,9`"))?;
+        let parse = |coordinate: &str| {
+            coordinate
+                .parse()
+                .map_err(|error: ParseError| error.within(s, coordinate))
+        };
+        let line @ Self(Coordinate(x1, y1), Coordinate(x2, y2)) = Self(parse(start)?, parse(end)?);
+        if line.is_diagonal() && x1.abs_diff(x2) != y1.abs_diff(y2) {
+            return Err(ParseError::at(
+                s,
+                s,
+                "a horizontal, vertical or diagonal line",
+            ));
+        }
+        Ok(line)
     }
 }
 
@@ -120,52 +130,73 @@ fn range(start: usize, end: usize) -> impl Iterator<Item = usize> {
 }
 
 #[derive(Clone, Copy, Debug, Default)]
-struct Coordinate(usize, usize);
+pub struct Coordinate(usize, usize);
 
 impl FromStr for Coordinate {
-    type Err = InvalidInputError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').ok_or(InvalidInputError)?;
-        Ok(Self(x.parse()?, y.parse()?))
-    }
-}
-
-#[derive(Debug)]
-struct InvalidInputError;
-
-impl From<ParseIntError> for InvalidInputError {
-    fn from(_: ParseIntError) -> Self {
-        Self
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::at(s, s, "a coordinate like `0,9`"))?;
+        let parse = |coordinate, expected| {
+            parse_token(s, coordinate, expected)
+                .ok()
+                .filter(|&coordinate| coordinate <= MAX_COORDINATE)
+                .ok_or_else(|| {
+                    ParseError::at(s, coordinate, format!("{expected} up to {MAX_COORDINATE}"))
+                })
+        };
+        Ok(Self(
+            parse(x, "an x coordinate")?,
+            parse(y, "a y coordinate")?,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test() {
-        assert_eq!(part_1::<10>(SAMPLE_INPUT).unwrap(), 5);
-        assert_eq!(part_1::<500>(INPUT).unwrap(), 5_084);
+        assert_eq!(part_1(&parse_input(SAMPLE_INPUT).unwrap()), 5);
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()), 5_084);
 
-        assert_eq!(part_2::<10>(SAMPLE_INPUT).unwrap(), 12);
-        assert_eq!(part_2::<500>(INPUT).unwrap(), 17_882);
+        assert_eq!(part_2(&parse_input(SAMPLE_INPUT).unwrap()), 12);
+        assert_eq!(part_2(&parse_input(INPUT).unwrap()), 17_882);
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1::<500>(INPUT));
-    }
-
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2::<500>(INPUT));
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_input("0,9 -> 5,9\n8,0 -> 0,x8\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 10: expected a y coordinate up to 4095, found `x8`"
+        );
+        assert_eq!(
+            parse_input("0,0 -> 3000000,3000000\n")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 8: expected an x coordinate up to 4095, found `3000000`"
+        );
+        assert_eq!(
+            parse_input("18446744073709551615,0 -> 0,0\n")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 1: expected an x coordinate up to 4095, found `18446744073709551615`"
+        );
+        assert_eq!(
+            parse_input("3,9 -> 4,3\n").unwrap_err().to_string(),
+            "line 1, column 1: expected a horizontal, vertical or diagonal line, found `3,9 -> 4,3`"
+        );
+        assert_eq!(
+            part_2(&parse_input("4095,4095 -> 4094,4094\n4095,4095 -> 4095,4095\n").unwrap()),
+            1
+        );
     }
 }
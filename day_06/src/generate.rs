@@ -0,0 +1,24 @@
+use aoc_core::Rng;
+
+/// A school of `size` lanternfish, with internal timers from 1 to 5 like in
+/// the puzzle input.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let timers: Vec<_> = (0..size).map(|_| rng.range(1..=5).to_string()).collect();
+    timers.join(",") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day06;
+    use aoc_core::{Answer, Part, Solution};
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 300);
+        assert!(matches!(
+            Day06::solve(&input, Part::Two),
+            Ok(Answer::Integer(num_fish)) if num_fish > 300
+        ));
+    }
+}
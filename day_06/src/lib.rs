@@ -1,15 +1,38 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
+
+mod generate;
+
+use aoc_core::{Answer, ParseError, Solution};
+pub use generate::generate;
 
 use std::str::FromStr;
 
-fn part_1(input: &str) -> usize {
-    num_fish_after_n_days(input.parse().unwrap(), 80)
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input<'a> = School;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        input.parse()
+    }
+
+    fn part_1(school: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(school).into())
+    }
+
+    fn part_2(school: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(school).into())
+    }
+}
+
+fn part_1(school: &School) -> usize {
+    num_fish_after_n_days(school.clone(), 80)
 }
 
-fn part_2(input: &str) -> usize {
-    num_fish_after_n_days(input.parse().unwrap(), 256)
+fn part_2(school: &School) -> usize {
+    num_fish_after_n_days(school.clone(), 256)
 }
 
 fn num_fish_after_n_days(mut school: School, n: usize) -> usize {
@@ -19,8 +42,8 @@ fn num_fish_after_n_days(mut school: School, n: usize) -> usize {
     school.len()
 }
 
-#[derive(Debug)]
-struct School([usize; 9]);
+#[derive(Clone, Debug)]
+pub struct School([usize; 9]);
 
 impl School {
     fn advance_one_day(&mut self) {
@@ -38,13 +61,17 @@ impl School {
 }
 
 impl FromStr for School {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = s.as_bytes();
         let mut counts = [0; 9];
-        for i in (0..bytes.len()).step_by(2) {
-            counts[(bytes[i] - b'0') as usize] += 1;
+        for timer in s.trim_end().split(',') {
+            let count = timer
+                .parse()
+                .ok()
+                .and_then(|timer: usize| counts.get_mut(timer))
+                .ok_or_else(|| ParseError::at(s, timer, "a timer from 0 to 8"))?;
+            *count += 1;
         }
         Ok(Self(counts))
     }
@@ -52,30 +79,55 @@ impl FromStr for School {
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
+    use aoc_core::Rng;
+    use std::iter;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test() {
-        assert_eq!(part_1(SAMPLE_INPUT), 5_934);
-        assert_eq!(part_1(INPUT), 380_612);
+        assert_eq!(part_1(&SAMPLE_INPUT.parse().unwrap()), 5_934);
+        assert_eq!(part_1(&INPUT.parse().unwrap()), 380_612);
 
-        assert_eq!(part_2(SAMPLE_INPUT), 26_984_457_539);
-        assert_eq!(part_2(INPUT), 1_710_166_656_900);
+        assert_eq!(part_2(&SAMPLE_INPUT.parse().unwrap()), 26_984_457_539);
+        assert_eq!(part_2(&INPUT.parse().unwrap()), 1_710_166_656_900);
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT));
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            "3,4,9,1\n".parse::<School>().unwrap_err().to_string(),
+            "line 1, column 5: expected a timer from 0 to 8, found `9`"
+        );
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT));
+    // Simulates every fish on its own, like the puzzle describes
+    fn simulate_naively(input: &str, days: usize) -> usize {
+        let mut timers: Vec<usize> = input
+            .trim_end()
+            .split(',')
+            .map(|timer| timer.parse().unwrap())
+            .collect();
+        for _ in 0..days {
+            let num_new = timers.iter().filter(|&&timer| timer == 0).count();
+            for timer in &mut timers {
+                *timer = timer.checked_sub(1).unwrap_or(6);
+            }
+            timers.extend(iter::repeat_n(8, num_new));
+        }
+        timers.len()
+    }
+
+    #[test]
+    fn test_against_naive() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 5);
+            assert_eq!(
+                part_1(&input.parse().unwrap()),
+                simulate_naively(&input, 80)
+            );
+        }
     }
 }
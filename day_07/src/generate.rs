@@ -0,0 +1,12 @@
+use aoc_core::Rng;
+
+use crate::MAX_POSITION;
+
+/// The horizontal positions of `size` crab submarines, from 0 to `2 * size`
+/// (or the largest position there can be).
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let positions: Vec<_> = (0..size)
+        .map(|_| rng.range(0..=MAX_POSITION.min(2 * size)).to_string())
+        .collect();
+    positions.join(",") + "\n"
+}
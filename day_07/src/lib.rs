@@ -1,46 +1,97 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(int_abs_diff, test)]
+
+mod generate;
+
+use aoc_core::{parse_token, Answer, ParseError, Solution};
+pub use generate::generate;
 
 use std::convert::identity;
 
-fn part_1(input: &str) -> usize {
-    find_minimal_fuel_cost(&parse_input(input), identity)
+// Positions are counted in a vector, so they can't be arbitrarily large
+pub const MAX_POSITION: usize = 1_000_000;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'a> = Vec<usize>;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part_1(positions: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(positions).into())
+    }
+
+    fn part_2(positions: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(positions).into())
+    }
+}
+
+fn part_1(crab_submarine_positions: &[usize]) -> usize {
+    find_minimal_fuel_cost(crab_submarine_positions, identity)
 }
 
-fn part_2(input: &str) -> usize {
+fn part_2(crab_submarine_positions: &[usize]) -> usize {
     find_minimal_fuel_cost(
-        &parse_input(input),
+        crab_submarine_positions,
         |n| n * (n + 1) / 2, // https://en.wikipedia.org/wiki/Triangular_number
     )
 }
 
 fn find_minimal_fuel_cost(
-    crab_submarine_positions: &[u8],
+    crab_submarine_positions: &[usize],
     calculate_fuel_cost_for_distance: fn(usize) -> usize,
 ) -> usize {
-    (0..=crab_submarine_positions.len())
-        .map(|target_position| {
-            calculate_total_fuel_cost(
-                crab_submarine_positions,
-                target_position,
-                calculate_fuel_cost_for_distance,
-            )
-        })
-        .min()
-        .unwrap()
+    let total_fuel_cost = |target_position| {
+        calculate_total_fuel_cost(
+            crab_submarine_positions,
+            target_position,
+            calculate_fuel_cost_for_distance,
+        )
+    };
+    // Fuel costs only grow with the distance, and never by less than they did
+    // for the step before, so the total cost goes down until the cheapest
+    // position and then up, and the cheapest one can be bisected for
+    let (mut low, mut high) = (0, crab_submarine_positions.len() - 1);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if total_fuel_cost(middle) > total_fuel_cost(middle + 1) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    total_fuel_cost(low)
 }
 
-fn parse_input(input: &str) -> [u8; 2_000] {
-    let mut positions = [0; 2_000];
-    for position in input.split(',') {
-        positions[position.parse::<usize>().unwrap()] += 1;
+// Counts the number of crab submarines at each position
+fn parse_input(input: &str) -> Result<Vec<usize>, ParseError> {
+    let crab_submarines: Vec<usize> = input
+        .trim_end()
+        .split(',')
+        .map(|position| {
+            parse_token(input, position, "a position")
+                .ok()
+                .filter(|&position| position <= MAX_POSITION)
+                .ok_or_else(|| {
+                    ParseError::at(input, position, format!("a position up to {MAX_POSITION}"))
+                })
+        })
+        .collect::<Result<_, _>>()?;
+    let max_position = crab_submarines.iter().copied().max().unwrap_or_default();
+
+    let mut positions = vec![0; max_position + 1];
+    for position in crab_submarines {
+        positions[position] += 1;
     }
-    positions
+    Ok(positions)
 }
 
 fn calculate_total_fuel_cost(
-    positions: &[u8],
+    positions: &[usize],
     target_position: usize,
     fuel_cost_for_distance: fn(usize) -> usize,
 ) -> usize {
@@ -48,37 +99,67 @@ fn calculate_total_fuel_cost(
         .iter()
         .enumerate()
         .fold(0, |cost, (position, &num_crab_submarines)| {
-            cost + (num_crab_submarines as usize)
-                * fuel_cost_for_distance(position.abs_diff(target_position))
+            cost + num_crab_submarines * fuel_cost_for_distance(position.abs_diff(target_position))
         })
 }
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
+    use aoc_core::Rng;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test() {
-        assert_eq!(part_1(SAMPLE_INPUT), 37);
-        assert_eq!(part_1(INPUT), 349_357);
+        assert_eq!(part_1(&parse_input(SAMPLE_INPUT).unwrap()), 37);
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()), 349_357);
 
-        assert_eq!(part_2(SAMPLE_INPUT), 168);
-        assert_eq!(part_2(INPUT), 96_708_205);
+        assert_eq!(part_2(&parse_input(SAMPLE_INPUT).unwrap()), 168);
+        assert_eq!(part_2(&parse_input(INPUT).unwrap()), 96_708_205);
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT));
+    // Tries every position, for every crab submarine on its own
+    fn minimal_fuel_naively(positions: &[usize], cost: impl Fn(usize) -> usize) -> usize {
+        let max = positions.iter().copied().max().unwrap();
+        (0..=max)
+            .map(|target| {
+                positions
+                    .iter()
+                    .map(|&position| cost(position.abs_diff(target)))
+                    .sum()
+            })
+            .min()
+            .unwrap()
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT));
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_input("16,1,1000001,0\n").unwrap_err().to_string(),
+            "line 1, column 6: expected a position up to 1000000, found `1000001`"
+        );
+    }
+
+    #[test]
+    fn test_against_naive() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 50);
+            let crab_submarines: Vec<usize> = input
+                .trim_end()
+                .split(',')
+                .map(|position| position.parse().unwrap())
+                .collect();
+            let positions = parse_input(&input).unwrap();
+            assert_eq!(
+                part_1(&positions),
+                minimal_fuel_naively(&crab_submarines, |distance| distance)
+            );
+            assert_eq!(
+                part_2(&positions),
+                minimal_fuel_naively(&crab_submarines, |distance| distance * (distance + 1) / 2)
+            );
+        }
     }
 }
@@ -0,0 +1,46 @@
+use aoc_core::Rng;
+use std::fmt::Write;
+
+// The segments of each digit, before they got mixed up
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// `size` notes, each with its own mix-up of the segment wires.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::with_capacity(90 * size);
+    for _ in 0..size {
+        let mut wires = *b"abcdefg";
+        rng.shuffle(&mut wires);
+        let mut digits: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut digits);
+        let signal_patterns: Vec<_> = digits
+            .iter()
+            .map(|&digit| pattern(rng, wires, digit))
+            .collect();
+        let output_values: Vec<_> = (0..4)
+            .map(|_| {
+                let digit = rng.below(10);
+                pattern(rng, wires, digit)
+            })
+            .collect();
+        writeln!(
+            input,
+            "{} | {}",
+            signal_patterns.join(" "),
+            output_values.join(" ")
+        )
+        .expect("writing to a String can't fail");
+    }
+    input
+}
+
+// The segments of `digit`, on the mixed up `wires`, in any order
+fn pattern(rng: &mut Rng, wires: [u8; 7], digit: usize) -> String {
+    let mut segments: Vec<u8> = DIGITS[digit]
+        .bytes()
+        .map(|segment| wires[usize::from(segment - b'a')])
+        .collect();
+    rng.shuffle(&mut segments);
+    String::from_utf8(segments).expect("segments are ASCII")
+}
@@ -1,58 +1,110 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
+
+mod generate;
+
+use aoc_core::{parse_lines, Answer, ParseError, Solution, SolveError};
+pub use generate::generate;
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
 
 const NUM_SEGMENTS: [u8; 10] = [6, 2, 5, 5, 4, 5, 6, 3, 7, 8];
 
-fn part_1(input: &str) -> usize {
-    parse_input(input)
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Vec<Entry>;
+    type Error = SolveError<DisplayError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(entries: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(entries).into())
+    }
+
+    fn part_2(entries: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(entries).map_err(SolveError::Solve)?.into())
+    }
+}
+
+fn part_1(entries: &[Entry]) -> usize {
+    entries
+        .iter()
         .flat_map(|(_, digit_output_values)| digit_output_values)
         .filter(|digit_output_value| [2, 4, 3, 7].contains(&digit_output_value.len()))
         .count()
 }
 
-fn part_2(input: &str) -> usize {
-    parse_input(input).map(deduce_output_value).sum()
+fn part_2(entries: &[Entry]) -> Result<usize, DisplayError> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            deduce_output_value(entry).ok_or(DisplayError::NoWiring { line: index + 1 })
+        })
+        .sum()
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = ([String; 10], [String; 4])> + '_ {
-    input.lines().map(|line| {
-        let (signal_patterns, digit_output_values) = line.split_once(" | ").unwrap();
-        (
-            to_array_of_sorted_strings(signal_patterns),
-            to_array_of_sorted_strings(digit_output_values),
-        )
+pub type Entry = ([String; 10], [String; 4]);
+
+fn parse_input(input: &str) -> Result<Vec<Entry>, ParseError> {
+    parse_lines(input, |line| {
+        let (signal_patterns, digit_output_values) = line.split_once(" | ").ok_or_else(|| {
+            ParseError::at(
+                line,
+                line,
+                "signal patterns and output values separated by ` | `",
+            )
+        })?;
+        Ok((
+            to_array_of_sorted_strings(line, signal_patterns)?,
+            to_array_of_sorted_strings(line, digit_output_values)?,
+        ))
     })
 }
 
-fn to_array_of_sorted_strings<const N: usize>(input: &str) -> [String; N]
+// `part` is a slice of `line`, for error positions
+fn to_array_of_sorted_strings<const N: usize>(
+    line: &str,
+    part: &str,
+) -> Result<[String; N], ParseError>
 where
     [String; N]: Default,
 {
-    let mut iterator = input.split(' ').map(|s| {
-        let mut bytes = s.as_bytes().to_vec();
-        bytes.sort_unstable();
-        unsafe { String::from_utf8_unchecked(bytes) }
-    });
-
+    let mut iterator = part.split(' ');
     let mut array: [String; N] = Default::default();
     for element in &mut array {
-        *element = iterator.next().unwrap();
+        let pattern = iterator
+            .next()
+            .ok_or_else(|| ParseError::at(line, &part[part.len()..], format!("{N} patterns")))?;
+        if pattern.is_empty() || !pattern.bytes().all(|byte| (b'a'..=b'g').contains(&byte)) {
+            return Err(ParseError::at(line, pattern, "segments from `a` to `g`"));
+        }
+        let mut bytes = pattern.as_bytes().to_vec();
+        bytes.sort_unstable();
+        *element = String::from_utf8(bytes).expect("segments are ASCII");
+    }
+    match iterator.next() {
+        Some(extra) => Err(ParseError::at(line, extra, format!("only {N} patterns"))),
+        None => Ok(array),
     }
-    array
 }
 
 const EMPTY_STRING: String = String::new();
 
+// `None` if the signal patterns can't be the ten digits, however the wires
+// are mixed up
 #[allow(clippy::too_many_lines)]
-fn deduce_output_value(
-    (signal_patterns, digit_output_values): ([String; 10], [String; 4]),
-) -> usize {
+fn deduce_output_value((signal_patterns, digit_output_values): &Entry) -> Option<usize> {
     let find_by_length = |length| {
         signal_patterns
             .iter()
             .find(|pattern| pattern.len() == length)
-            .unwrap()
     };
     let filter_by_length = |length| {
         let mut iterator = signal_patterns
@@ -60,103 +112,128 @@ fn deduce_output_value(
             .filter(|&pattern| pattern.len() == length);
 
         let mut array = [&signal_patterns[0]; 3];
-        for i in 0..3 {
-            array[i] = iterator.next().unwrap();
+        for element in &mut array {
+            *element = iterator.next()?;
         }
-        array
+        Some(array)
     };
 
     let empty_string = String::new(); // TODO make constant
     let mut patterns: [&String; 10] = [&empty_string; 10];
 
     // Unique length digits
-    patterns[1] = find_by_length(2);
-    patterns[4] = find_by_length(4);
-    patterns[7] = find_by_length(3);
-    patterns[8] = find_by_length(7);
+    patterns[1] = find_by_length(2)?;
+    patterns[4] = find_by_length(4)?;
+    patterns[7] = find_by_length(3)?;
+    patterns[8] = find_by_length(7)?;
 
-    let two_three_or_five = filter_by_length(5);
-    patterns[3] = pick_by_match(&two_three_or_five, patterns[1]);
+    let two_three_or_five = filter_by_length(5)?;
+    patterns[3] = pick_by_match(&two_three_or_five, patterns[1])?;
     let four_sans_one: String = patterns[4]
         .chars()
         .filter(|&c| !patterns[1].contains(c))
         .collect();
-    patterns[5] = two_three_or_five
-        .iter()
-        .find(|&&pattern| {
-            pattern != patterns[3] && four_sans_one.chars().all(|c| pattern.contains(c))
-        })
-        .unwrap();
-    patterns[2] = pick_by_exclusion(&two_three_or_five, &[patterns[3], patterns[5]]);
+    patterns[5] = two_three_or_five.iter().find(|&&pattern| {
+        pattern != patterns[3] && four_sans_one.chars().all(|c| pattern.contains(c))
+    })?;
+    patterns[2] = pick_by_exclusion(&two_three_or_five, &[patterns[3], patterns[5]])?;
 
-    let zero_six_or_nine = filter_by_length(6);
+    let zero_six_or_nine = filter_by_length(6)?;
     patterns[6] = zero_six_or_nine
         .iter()
-        .find(|pattern| !patterns[1].chars().all(|c| pattern.contains(c)))
-        .unwrap();
-    patterns[9] = zero_six_or_nine
-        .iter()
-        .find(|&&pattern| {
-            pattern != patterns[6] && four_sans_one.chars().all(|c| pattern.contains(c))
-        })
-        .unwrap();
-    patterns[0] = pick_by_exclusion(&zero_six_or_nine, &[patterns[6], patterns[9]]);
+        .find(|pattern| !patterns[1].chars().all(|c| pattern.contains(c)))?;
+    patterns[9] = zero_six_or_nine.iter().find(|&&pattern| {
+        pattern != patterns[6] && four_sans_one.chars().all(|c| pattern.contains(c))
+    })?;
+    patterns[0] = pick_by_exclusion(&zero_six_or_nine, &[patterns[6], patterns[9]])?;
+
+    // Every pattern has to be a different digit, or the deductions above
+    // picked one twice
+    if (1..patterns.len()).any(|index| patterns[..index].contains(&patterns[index])) {
+        return None;
+    }
 
     digit_output_values
         .iter()
-        .rev()
-        .enumerate()
-        .fold(0, |result, (index, digit_output_value)| {
-            result
-                + patterns
-                    .iter()
-                    .position(|&pattern| pattern == digit_output_value)
-                    .unwrap()
-                    * 10_usize.pow(index as u32)
+        .try_fold(0, |result, digit_output_value| {
+            let digit = patterns
+                .iter()
+                .position(|&pattern| pattern == digit_output_value)?;
+            Some(result * 10 + digit)
         })
 }
 
-fn pick_by_match<'a>(patterns: &[&'a String], match_pattern: &str) -> &'a String {
+fn pick_by_match<'a>(patterns: &[&'a String], match_pattern: &str) -> Option<&'a String> {
     patterns
         .iter()
         .find(|pattern| match_pattern.chars().all(|c| pattern.contains(c)))
-        .unwrap()
+        .copied()
 }
 
-fn pick_by_exclusion<'a>(patterns: &[&'a String], excluded: &[&'a String]) -> &'a String {
+fn pick_by_exclusion<'a>(patterns: &[&'a String], excluded: &[&'a String]) -> Option<&'a String> {
     patterns
         .iter()
         .find(|pattern| !excluded.contains(pattern))
-        .unwrap()
+        .copied()
 }
 
+#[derive(Debug)]
+pub enum DisplayError {
+    /// The signal patterns of an entry aren't the ten digits, whichever way
+    /// the wires are mixed up. Lines count from 1.
+    NoWiring { line: usize },
+}
+
+impl Display for DisplayError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::NoWiring { line } => write!(
+                formatter,
+                "line {line}: the signal patterns don't match any wiring of the digits"
+            ),
+        }
+    }
+}
+
+impl Error for DisplayError {}
+
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test() {
-        assert_eq!(part_1(SAMPLE_INPUT), 26);
-        assert_eq!(part_1(INPUT), 504);
-
-        assert_eq!(part_2("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf"), 5353);
-        assert_eq!(part_2(SAMPLE_INPUT), 61_229);
-        assert_eq!(part_2(INPUT), 1_073_431);
-    }
-
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT));
+        assert_eq!(part_1(&parse_input(SAMPLE_INPUT).unwrap()), 26);
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()), 504);
+
+        assert_eq!(part_2(&parse_input("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf").unwrap()).unwrap(), 5353);
+        assert_eq!(part_2(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(), 61_229);
+        assert_eq!(part_2(&parse_input(INPUT).unwrap()).unwrap(), 1_073_431);
+        assert_eq!(
+            part_2(&parse_input("a c b d g b f b d e | c e e d").unwrap())
+                .unwrap_err()
+                .to_string(),
+            "line 1: the signal patterns don't match any wiring of the digits"
+        );
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT));
+    #[test]
+    fn test_parse_errors() {
+        let parse_error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            parse_error("ab cd ef ga bc de fg ab cd ef | ab cd ef ga\nab cd"),
+            "line 2, column 1: expected signal patterns and output values separated by ` | `, found `ab cd`"
+        );
+        assert_eq!(
+            parse_error("ab cd ef ga bc de fg ab cd ef | ab cd ef"),
+            "line 1, column 41: expected 4 patterns, found nothing"
+        );
+        assert_eq!(
+            parse_error("ab cd ef ga bc de fg ab cx ef | ab cd ef ga"),
+            "line 1, column 25: expected segments from `a` to `g`, found `cx`"
+        );
     }
 }
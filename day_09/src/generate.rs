@@ -0,0 +1,30 @@
+use aoc_core::Rng;
+
+/// A `size` by `size` height map.
+///
+/// About one in four locations has height 9, so the basins stay small like in
+/// the puzzle input.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::with_capacity((size + 1) * size);
+    for _ in 0..size {
+        input.extend((0..size).map(|_| {
+            if rng.chance(1, 4) {
+                '9'
+            } else {
+                char::from(*rng.choose(b"012345678"))
+            }
+        }));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(generate(&mut Rng::new(1), 100).lines().count(), 100);
+    }
+}
@@ -1,14 +1,34 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
 
-use std::{
-    ops::{Index, IndexMut},
-    str::FromStr,
-};
+mod generate;
 
-fn part_1<const R: usize, const C: usize>(input: &str) -> usize {
-    let height_map: HeightMap<R, C> = input.parse().unwrap();
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_grid::{Coordinate, Grid};
+pub use generate::generate;
+
+use std::{ops::Index, str::FromStr};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = HeightMap;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        input.parse()
+    }
+
+    fn part_1(height_map: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(height_map).into())
+    }
+
+    fn part_2(height_map: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(height_map).into())
+    }
+}
+
+fn part_1(height_map: &HeightMap) -> usize {
     height_map
         .low_points()
         .map(|coordinate| height_map[coordinate] as usize + 1)
@@ -16,8 +36,8 @@ fn part_1<const R: usize, const C: usize>(input: &str) -> usize {
 }
 
 #[allow(clippy::needless_collect)] // Need to collect low_points, because otherwise there is aliasing
-fn part_2<const R: usize, const C: usize>(input: &str) -> usize {
-    let mut height_map: HeightMap<R, C> = input.parse().unwrap();
+fn part_2(height_map: &HeightMap) -> usize {
+    let mut height_map = height_map.clone();
     let low_points: Vec<_> = height_map.low_points().collect();
     let basin_sizes = low_points
         .into_iter()
@@ -26,15 +46,16 @@ fn part_2<const R: usize, const C: usize>(input: &str) -> usize {
 }
 
 #[derive(Clone, Debug)]
-struct HeightMap<const R: usize, const C: usize>([[u8; C]; R]);
-
-impl<const R: usize, const C: usize> HeightMap<R, C> {
-    fn basin_size(&mut self, coordinate: (usize, usize)) -> usize {
-        let height = self[coordinate];
-        self[coordinate] = u8::MAX; // Mark as seen
-        Self::adjacent_points(coordinate)
+pub struct HeightMap(Grid<u8>);
+
+impl HeightMap {
+    fn basin_size(&mut self, coordinate: Coordinate) -> usize {
+        let height = self.0[coordinate];
+        self.0[coordinate] = u8::MAX; // Mark as seen
+        self.0
+            .neighbours_4(coordinate)
             .filter_map(|c| {
-                let adjacent_height = self[c];
+                let adjacent_height = self.0[c];
                 if adjacent_height > height && adjacent_height < 9 {
                     Some(self.basin_size(c)) // Recursion FTW
                 } else {
@@ -45,60 +66,28 @@ impl<const R: usize, const C: usize> HeightMap<R, C> {
             + 1
     }
 
-    fn low_points(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
-        (0..R)
-            .flat_map(|row| (0..C).map(move |column| (row, column)))
-            .filter(|&coordinate| {
-                Self::adjacent_points(coordinate)
-                    .all(|adjecent_point| self[adjecent_point] > self[coordinate])
-            })
-    }
-
-    fn adjacent_points((row, column): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
-        [
-            row.checked_sub(1).map(|r| (r, column)),
-            if row + 1 < R {
-                Some((row + 1, column))
-            } else {
-                None
-            },
-            column.checked_sub(1).map(|c| (row, c)),
-            if column + 1 < C {
-                Some((row, column + 1))
-            } else {
-                None
-            },
-        ]
-        .into_iter()
-        .flatten()
+    fn low_points(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.0.coordinates().filter(|&coordinate| {
+            self.0
+                .neighbours_4(coordinate)
+                .all(|adjecent_point| self.0[adjecent_point] > self.0[coordinate])
+        })
     }
 }
 
-impl<const R: usize, const C: usize> Index<(usize, usize)> for HeightMap<R, C> {
+impl Index<Coordinate> for HeightMap {
     type Output = u8;
 
-    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
-        &self.0[row][column]
+    fn index(&self, coordinate: Coordinate) -> &Self::Output {
+        &self.0[coordinate]
     }
 }
 
-impl<const R: usize, const C: usize> IndexMut<(usize, usize)> for HeightMap<R, C> {
-    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut u8 {
-        &mut self.0[row][column]
-    }
-}
+impl FromStr for HeightMap {
+    type Err = ParseError;
 
-impl<const R: usize, const C: usize> FromStr for HeightMap<R, C> {
-    type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut grid = [[0; C]; R];
-        let mut heights = s.lines().flat_map(str::bytes).map(|byte| byte - b'0');
-        for row in &mut grid {
-            for height in row {
-                *height = heights.next().unwrap();
-            }
-        }
-        Ok(Self(grid))
+        s.parse().map(Self)
     }
 }
 
@@ -121,30 +110,17 @@ fn top_3(iterator: impl Iterator<Item = usize>) -> [usize; 3] {
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test() {
-        assert_eq!(part_1::<5, 10>(SAMPLE_INPUT), 15);
-        assert_eq!(part_1::<100, 100>(INPUT), 537);
-
-        assert_eq!(part_2::<5, 10>(SAMPLE_INPUT), 1_134);
-        assert_eq!(part_2::<100, 100>(INPUT), 1_142_757);
-    }
-
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1::<100, 100>(INPUT));
-    }
+        assert_eq!(part_1(&SAMPLE_INPUT.parse().unwrap()), 15);
+        assert_eq!(part_1(&INPUT.parse().unwrap()), 537);
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2::<100, 100>(INPUT));
+        assert_eq!(part_2(&SAMPLE_INPUT.parse().unwrap()), 1_134);
+        assert_eq!(part_2(&INPUT.parse().unwrap()), 1_142_757);
     }
 }
@@ -0,0 +1,42 @@
+use aoc_core::Rng;
+
+const OPENING: [u8; 4] = *b"([{<";
+const CLOSING: [u8; 4] = *b")]}>";
+// Keeps the autocomplete scores within 64 bits, since 5^27 > 2^64
+const MAX_DEPTH: usize = 20;
+
+/// `size` lines of the navigation subsystem, each corrupted or incomplete.
+///
+/// The first line is always incomplete, so there is a middle score.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::with_capacity(100 * size);
+    for line in 0..size {
+        let corrupted = line > 0 && rng.chance(1, 2);
+        let length = rng.range(10..=100);
+        let mut expected = Vec::with_capacity(MAX_DEPTH);
+        for _ in 0..length {
+            let close = expected.len() == MAX_DEPTH || rng.chance(1, 2);
+            match expected.last() {
+                Some(&closing) if close => {
+                    expected.pop();
+                    input.push(char::from(closing));
+                }
+                _ => {
+                    let kind = rng.below(4);
+                    input.push(char::from(OPENING[kind]));
+                    expected.push(CLOSING[kind]);
+                }
+            }
+        }
+        if expected.is_empty() {
+            input.push('(');
+            expected.push(b')');
+        }
+        if let (true, Some(closing)) = (corrupted, expected.last()) {
+            let wrong: Vec<_> = CLOSING.iter().filter(|&c| c != closing).collect();
+            input.push(char::from(**rng.choose(&wrong)));
+        }
+        input.push('\n');
+    }
+    input
+}
@@ -1,36 +1,84 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
-
-fn part_1(input: &str) -> usize {
-    input
-        .lines()
-        .filter_map(|line| match parse_line(line) {
-            ParseResult::InvalidCharacter(c) => Some(c),
-            ParseResult::Incomplete(_) => None,
+
+mod generate;
+
+use aoc_core::{parse_lines, Answer, ParseError, Solution, SolveError};
+pub use generate::generate;
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Vec<ParseResult>;
+    type Error = SolveError<SyntaxError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(parse_results: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(parse_results).into())
+    }
+
+    fn part_2(parse_results: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(parse_results).map_err(SolveError::Solve)?.into())
+    }
+}
+
+fn part_1(parse_results: &[ParseResult]) -> usize {
+    parse_results
+        .iter()
+        .filter_map(|parse_result| match parse_result {
+            ParseResult::InvalidCharacter(c) => Some(*c),
+            ParseResult::Incomplete(_) | ParseResult::Complete => None,
         })
         .map(syntax_error_points)
         .sum()
 }
 
-fn part_2(input: &str) -> usize {
-    let mut scores: Vec<_> = input
-        .lines()
-        .filter_map(|line| match parse_line(line) {
-            ParseResult::InvalidCharacter(_) => None,
-            ParseResult::Incomplete(completion_characters) => Some(completion_characters),
+fn part_2(parse_results: &[ParseResult]) -> Result<usize, SyntaxError> {
+    let mut scores = parse_results
+        .iter()
+        .enumerate()
+        .filter_map(|(index, parse_result)| match parse_result {
+            ParseResult::InvalidCharacter(_) | ParseResult::Complete => None,
+            ParseResult::Incomplete(completion_characters) => Some((index, completion_characters)),
         })
-        .map(|completion_characters| {
+        .map(|(index, completion_characters)| {
             completion_characters
                 .iter()
                 .rev()
-                .fold(0, |score, &character| {
-                    score * 5 + autocomplete_points(character)
+                .try_fold(0_usize, |score, &character| {
+                    score
+                        .checked_mul(5)?
+                        .checked_add(autocomplete_points(character))
                 })
+                .ok_or(SyntaxError::ScoreOverflow { line: index + 1 })
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
+    if scores.is_empty() {
+        return Err(SyntaxError::NoIncompleteLines);
+    }
     let middle = scores.len() / 2;
-    *scores.select_nth_unstable(middle).1
+    Ok(*scores.select_nth_unstable(middle).1)
+}
+
+fn parse_input(input: &str) -> Result<Vec<ParseResult>, ParseError> {
+    parse_lines(input, |line| {
+        match line.char_indices().find(|&(_, c)| !"()[]{}<>".contains(c)) {
+            Some((index, c)) => Err(ParseError::at(
+                line,
+                &line[index..index + c.len_utf8()],
+                "a bracket",
+            )),
+            None => Ok(parse_line(line)),
+        }
+    })
 }
 
 fn parse_line(line: &str) -> ParseResult {
@@ -38,23 +86,51 @@ fn parse_line(line: &str) -> ParseResult {
     for character in line.bytes() {
         if let Some(closing_character) = closing_character(character) {
             closing_characters.push(closing_character);
-        } else if let Some(expected) = closing_characters.pop() {
-            if expected != character {
-                return ParseResult::InvalidCharacter(character);
-            }
-        } else {
-            unimplemented!("Correct lines are not supported");
+        } else if closing_characters.pop() != Some(character) {
+            // Closing a chunk that was never opened is just as corrupted
+            return ParseResult::InvalidCharacter(character);
         }
     }
 
-    ParseResult::Incomplete(closing_characters)
+    if closing_characters.is_empty() {
+        ParseResult::Complete
+    } else {
+        ParseResult::Incomplete(closing_characters)
+    }
 }
 
-enum ParseResult {
+#[derive(Debug)]
+pub enum ParseResult {
     InvalidCharacter(u8),
     Incomplete(Vec<u8>),
+    Complete,
 }
 
+#[derive(Debug)]
+pub enum SyntaxError {
+    /// Part 2 needs at least one incomplete line to pick the middle score of.
+    NoIncompleteLines,
+    /// The autocomplete score of a line doesn't fit in a `usize`. Lines count
+    /// from 1.
+    ScoreOverflow { line: usize },
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::NoIncompleteLines => write!(formatter, "no line is incomplete"),
+            Self::ScoreOverflow { line } => {
+                write!(
+                    formatter,
+                    "line {line}: the autocomplete score is too large"
+                )
+            }
+        }
+    }
+}
+
+impl Error for SyntaxError {}
+
 fn closing_character(byte: u8) -> Option<u8> {
     match byte {
         b'(' => Some(b')'),
@@ -87,33 +163,69 @@ fn autocomplete_points(byte: u8) -> usize {
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
+    use aoc_core::Part;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(SAMPLE_INPUT), 26_397);
-        assert_eq!(part_1(INPUT), 462_693);
+        assert_eq!(part_1(&parse_input(SAMPLE_INPUT).unwrap()), 26_397);
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()), 462_693);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(SAMPLE_INPUT), 288_957);
-        assert_eq!(part_2(INPUT), 3_094_671_161);
+        assert_eq!(
+            part_2(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(),
+            288_957
+        );
+        assert_eq!(part_2(&parse_input(INPUT).unwrap()).unwrap(), 3_094_671_161);
+        assert!(matches!(
+            part_2(&parse_input("()\n(]\n").unwrap()),
+            Err(SyntaxError::NoIncompleteLines)
+        ));
+        assert_eq!(
+            Day10::solve("()", Part::Two).unwrap_err().to_string(),
+            "no line is incomplete"
+        );
+        assert_eq!(
+            part_2(&parse_input(&"<".repeat(27)).unwrap()).unwrap(),
+            5_usize.pow(27) - 1
+        );
+        assert_eq!(
+            Day10::solve(&format!("()\n{}", "<".repeat(28)), Part::Two)
+                .unwrap_err()
+                .to_string(),
+            "line 2: the autocomplete score is too large"
+        );
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT));
+    #[test]
+    fn test_parse_line() {
+        assert!(matches!(
+            parse_line("[<>({}){}[([])<>]]"),
+            ParseResult::Complete
+        ));
+        assert!(matches!(
+            parse_line("(]"),
+            ParseResult::InvalidCharacter(b']')
+        ));
+        assert!(matches!(
+            parse_line("()>"),
+            ParseResult::InvalidCharacter(b'>')
+        ));
+        assert!(matches!(parse_line("{(<"), ParseResult::Incomplete(closing) if closing == b"})>"));
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT));
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_input("[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<é[]>>(\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 18: expected a bracket, found `é`"
+        );
     }
 }
@@ -0,0 +1,26 @@
+use crate::Octopuses;
+use aoc_core::Rng;
+use aoc_grid::Grid;
+
+const MAX_STEPS: usize = 1_000;
+
+/// A `size` by `size` grid of octopus energy levels, which all flash at the
+/// same time within 1000 steps.
+///
+/// Random grids (and big ones especially) often never synchronise, so every
+/// few tries the energy levels come from a smaller range. A grid of just
+/// zeros and ones always synchronises, since the first flash spreads to
+/// every other octopus.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    for attempt in 0.. {
+        let num_levels = 10_usize.saturating_sub(attempt / 4).max(2);
+        let octopuses = Octopuses(Grid::from_fn(size, size, |_| {
+            u8::try_from(rng.below(num_levels)).unwrap_or_default()
+        }));
+        let mut simulation = octopuses.clone();
+        if (0..MAX_STEPS).any(|_| simulation.simulate_step() == size * size) {
+            return octopuses.to_string();
+        }
+    }
+    unreachable!("grids of zeros and ones always synchronise")
+}
@@ -1,37 +1,65 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(bool_to_option, test)]
+
+mod generate;
+
+use aoc_core::{Answer, ParseError, Solution, SolveError};
+use aoc_grid::{Coordinate, Grid};
+pub use generate::generate;
 
 use std::{
+    error::Error,
     fmt::{Display, Formatter, Result as FormatResult},
-    ops::{Index, IndexMut},
     str::FromStr,
 };
 
-fn part_1(input: &str) -> usize {
-    let mut grid: Grid = input.parse().unwrap();
+// Octopuses that don't flash at once by now likely never will
+const MAX_STEPS: usize = 10_000;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Octopuses;
+    type Error = SolveError<OctopusError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_1(octopuses: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(octopuses).into())
+    }
+
+    fn part_2(octopuses: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(octopuses).map_err(SolveError::Solve)?.into())
+    }
+}
+
+fn part_1(octopuses: &Octopuses) -> usize {
+    let mut octopuses = octopuses.clone();
     let mut num_flashes = 0;
     for _ in 0..100 {
-        num_flashes += grid.simulate_step();
+        num_flashes += octopuses.simulate_step();
     }
     num_flashes
 }
 
-fn part_2(input: &str) -> usize {
-    let mut grid: Grid = input.parse().unwrap();
-    for step in 0.. {
-        if grid.simulate_step() == 100 {
-            return step + 1;
+fn part_2(octopuses: &Octopuses) -> Result<usize, OctopusError> {
+    let mut octopuses = octopuses.clone();
+    for step in 0..MAX_STEPS {
+        if octopuses.simulate_step() == octopuses.0.len() {
+            return Ok(step + 1);
         }
     }
-    panic!("The bioluminescent dumbo octopuses did not synchronise their flash!")
+    Err(OctopusError::NoSynchronisedFlash)
 }
 
-struct Grid([[u8; 10]; 10]);
+#[derive(Clone)]
+pub struct Octopuses(Grid<u8>);
 
-impl Grid {
+impl Octopuses {
     fn simulate_step(&mut self) -> usize {
-        for octupus in self.all_octopuses() {
+        for octupus in self.0.iter_mut() {
             *octupus += 1;
         }
 
@@ -39,11 +67,11 @@ impl Grid {
         let mut will_flash = self.will_flash_next();
         while !will_flash.is_empty() {
             for coordinate in will_flash {
-                self[coordinate] = 0;
+                self.0[coordinate] = 0;
                 num_flashed += 1;
-                for adjacent_coordinate in adjacent_coordinates(coordinate) {
-                    if self[adjacent_coordinate] != 0 {
-                        self[adjacent_coordinate] += 1;
+                for adjacent_coordinate in self.0.neighbours_8(coordinate) {
+                    if self.0[adjacent_coordinate] != 0 {
+                        self.0[adjacent_coordinate] += 1;
                     }
                 }
             }
@@ -52,121 +80,77 @@ impl Grid {
         num_flashed
     }
 
-    fn all_octopuses(&mut self) -> impl Iterator<Item = &mut u8> {
-        self.0.iter_mut().flat_map(|row| row.iter_mut())
-    }
-
     fn will_flash_next(&self) -> Vec<Coordinate> {
-        all_coordinates()
-            .filter(|&coordinate| self[coordinate] != 0 && self[coordinate] > 9)
+        self.0
+            .coordinates()
+            .filter(|&coordinate| self.0[coordinate] > 9)
             .collect()
     }
 }
 
-impl FromStr for Grid {
-    type Err = ();
+impl FromStr for Octopuses {
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut grid: [[u8; 10]; 10] = Default::default();
-        let mut octopuses = input.lines().flat_map(str::bytes).map(|byte| byte - b'0');
-        for row in &mut grid {
-            for number in row {
-                *number = octopuses.next().unwrap();
-            }
-        }
-        Ok(Self(grid))
+        input.parse().map(Self)
     }
 }
 
-impl Index<Coordinate> for Grid {
-    type Output = u8;
-
-    fn index(&self, (row, column): Coordinate) -> &Self::Output {
-        &self.0[row][column]
+impl Display for Octopuses {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(formatter, "{}", self.0)
     }
 }
 
-impl IndexMut<Coordinate> for Grid {
-    fn index_mut(&mut self, (row, column): Coordinate) -> &mut Self::Output {
-        &mut self.0[row][column]
-    }
+#[derive(Debug)]
+pub enum OctopusError {
+    /// The octopuses didn't all flash at once in the first `MAX_STEPS` steps.
+    NoSynchronisedFlash,
 }
 
-impl Display for Grid {
+impl Display for OctopusError {
     fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
-        for line in self.0 {
-            let as_str = unsafe {
-                String::from_utf8_unchecked(
-                    line.iter()
-                        .map(|octopus| octopus + b'0')
-                        .collect::<Vec<_>>(),
-                )
-            };
-            writeln!(formatter, "{}", as_str)?;
+        match self {
+            Self::NoSynchronisedFlash => write!(
+                formatter,
+                "the octopuses didn't all flash at once in {MAX_STEPS} steps"
+            ),
         }
-        Ok(())
     }
 }
 
-type Coordinate = (usize, usize);
-
-fn all_coordinates() -> impl Iterator<Item = Coordinate> {
-    (0..10).flat_map(|row| (0..10).map(move |column| (row, column)))
-}
-
-#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
-fn adjacent_coordinates((row, column): Coordinate) -> impl Iterator<Item = Coordinate> {
-    [
-        (-1, -1),
-        (-1, 0),
-        (-1, 1),
-        (0, -1),
-        (0, 1),
-        (1, -1),
-        (1, 0),
-        (1, 1),
-    ]
-    .into_iter()
-    .filter_map(move |(delta_row, delta_column)| {
-        let row = row as isize + delta_row;
-        let column = column as isize + delta_column;
-        if (0..10).contains(&row) && (0..10).contains(&column) {
-            Some((row as usize, column as usize))
-        } else {
-            None
-        }
-    })
-}
+impl Error for OctopusError {}
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
+    use aoc_core::Rng;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(SAMPLE_INPUT), 1_656);
-        assert_eq!(part_1(INPUT), 1_694);
+        assert_eq!(part_1(&SAMPLE_INPUT.parse().unwrap()), 1_656);
+        assert_eq!(part_1(&INPUT.parse().unwrap()), 1_694);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(SAMPLE_INPUT), 195);
-        assert_eq!(part_2(INPUT), 346);
+        assert_eq!(part_2(&SAMPLE_INPUT.parse().unwrap()).unwrap(), 195);
+        assert_eq!(part_2(&INPUT.parse().unwrap()).unwrap(), 346);
+        assert!(matches!(
+            part_2(&"02".parse().unwrap()),
+            Err(OctopusError::NoSynchronisedFlash)
+        ));
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT));
-    }
-
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT));
+    #[test]
+    fn test_round_trip() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 10);
+            let octopuses: Octopuses = input.parse().unwrap();
+            assert_eq!(octopuses.to_string(), input);
+        }
     }
 }
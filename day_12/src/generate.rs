@@ -0,0 +1,71 @@
+use aoc_core::Rng;
+use std::{collections::HashSet, fmt::Write};
+
+/// A cave system with `size` caves besides `start` and `end`, about a
+/// quarter of them large.
+///
+/// Large caves are never connected to each other, since there would be
+/// endlessly many paths otherwise. The number of paths grows very quickly
+/// with `size`: the puzzle input has about a dozen caves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut caves = vec!["start".to_owned(), "end".to_owned()];
+    caves.extend((0..size).map(|index| {
+        if rng.chance(1, 4) {
+            name(index).to_uppercase()
+        } else {
+            name(index)
+        }
+    }));
+    let is_large = |cave: &str| cave.bytes().all(|c| c.is_ascii_uppercase());
+    let can_connect = |a: usize, b: usize| a != b && !(is_large(&caves[a]) && is_large(&caves[b]));
+
+    // Every cave connects to one before it, so they are all reachable, and
+    // then some more connections make for more paths
+    let mut connections = HashSet::new();
+    for cave in 1..caves.len() {
+        let other = loop {
+            let other = rng.below(cave);
+            if can_connect(cave, other) {
+                break other;
+            }
+        };
+        connections.insert((other, cave));
+    }
+    for _ in 0..size / 2 {
+        let (a, b) = (rng.below(caves.len()), rng.below(caves.len()));
+        if can_connect(a, b) && !connections.contains(&(b, a)) {
+            connections.insert((a, b));
+        }
+    }
+
+    let mut connections: Vec<_> = connections.into_iter().collect();
+    connections.sort_unstable();
+    rng.shuffle(&mut connections);
+    let mut input = String::with_capacity(8 * connections.len());
+    for (a, b) in connections {
+        writeln!(input, "{}-{}", caves[a], caves[b]).expect("writing to a String can't fail");
+    }
+    input
+}
+
+// Two or more lowercase letters, different for every index
+fn name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    while name.len() < 2 || index > 0 {
+        name.push(b'a' + u8::try_from(index % 26).expect("a letter"));
+        index /= 26;
+    }
+    String::from_utf8(name).expect("letters are ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name() {
+        assert_eq!(name(0), "aa");
+        assert_eq!(name(27), "bb");
+        assert_eq!(name(26 * 26), "aab");
+    }
+}
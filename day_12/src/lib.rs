@@ -1,52 +1,100 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
 
-use std::collections::HashMap;
+mod generate;
 
-fn part_1(input: &str) -> usize {
-    let connections = build_connections_map(input);
-    count_possible_paths(&connections, |path, cave| {
+use aoc_core::{parse_lines, Answer, ParseError, Solution, SolveError};
+pub use generate::generate;
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Connections<'a>;
+    type Error = SolveError<CaveError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(build_connections_map(input)?)
+    }
+
+    fn part_1(connections: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(connections).map_err(SolveError::Solve)?.into())
+    }
+
+    fn part_2(connections: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(connections).map_err(SolveError::Solve)?.into())
+    }
+}
+
+fn part_1(connections: &Connections) -> Result<usize, CaveError> {
+    count_possible_paths(connections, |path, cave| {
         cave.is_large() || !path.contains(&cave)
     })
 }
 
-fn part_2(input: &str) -> usize {
-    let connections = build_connections_map(input);
+fn part_2(connections: &Connections) -> Result<usize, CaveError> {
     let start = Cave::parse("start");
-    count_possible_paths(&connections, move |path, cave| {
+    count_possible_paths(connections, move |path, cave| {
         cave.is_large() || (cave != start && (!path.used_double_visit || !path.contains(&cave)))
     })
 }
 
-fn build_connections_map(input: &str) -> HashMap<Cave, Vec<Cave>> {
-    input
-        .lines()
-        .flat_map(|line| {
-            let (left, right) = line.split_once('-').unwrap();
-            let left = Cave::parse(left);
-            let right = Cave::parse(right);
-            [(left, right), (right, left)]
-        })
+pub type Connections<'a> = HashMap<Cave<'a>, Vec<Cave<'a>>>;
+
+fn build_connections_map(input: &str) -> Result<Connections<'_>, ParseError> {
+    let connections = parse_lines(input, |line| {
+        let (left, right) = line
+            .split_once('-')
+            .ok_or_else(|| ParseError::at(line, line, "two caves separated by `-`"))?;
+        for name in [left, right] {
+            if name.is_empty() || !name.bytes().all(|c| c.is_ascii_alphabetic()) {
+                return Err(ParseError::at(line, name, "a cave name"));
+            }
+        }
+        Ok((Cave::parse(left), Cave::parse(right)))
+    })?;
+    let start = Cave::parse("start");
+    if !connections
+        .iter()
+        .any(|&(left, right)| left == start || right == start)
+    {
+        let end = input.trim_end().len();
+        return Err(ParseError::at(
+            input,
+            &input[end..end],
+            "a connection to the `start` cave",
+        ));
+    }
+    Ok(connections
+        .into_iter()
+        .flat_map(|(left, right)| [(left, right), (right, left)])
         .fold(HashMap::with_capacity(15), |mut map, (from, to)| {
             map.entry(from).or_default().push(to);
             map
-        })
+        }))
 }
 
 fn count_possible_paths(
-    connections: &HashMap<Cave, Vec<Cave>>,
+    connections: &Connections,
     can_be_added: impl Fn(&Path, Cave) -> bool,
-) -> usize {
+) -> Result<usize, CaveError> {
     let start = Cave::parse("start");
     let end = Cave::parse("end");
+    if !connections.contains_key(&start) {
+        return Err(CaveError::NoStart);
+    }
     let mut candidate_paths = vec![Path::from(vec![start])];
     let mut num_possible_paths = 0;
 
     while let Some(candidate_path) = candidate_paths.pop() {
         let currently_at = candidate_path.last_cave();
-        let can_go_to = connections.get(currently_at).unwrap();
-        for &cave in can_go_to {
+        // Connections go both ways, so every cave a path reaches has some
+        for &cave in &connections[currently_at] {
             if cave == end {
                 num_possible_paths += 1;
             } else if can_be_added(&candidate_path, cave) {
@@ -55,11 +103,27 @@ fn count_possible_paths(
         }
     }
 
-    num_possible_paths
+    Ok(num_possible_paths)
+}
+
+#[derive(Debug)]
+pub enum CaveError {
+    /// No path can start, because nothing connects to the `start` cave.
+    NoStart,
+}
+
+impl Display for CaveError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::NoStart => write!(formatter, "there is no `start` cave"),
+        }
+    }
 }
 
+impl Error for CaveError {}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-enum Cave<'a> {
+pub enum Cave<'a> {
     Large(&'a str),
     Small(&'a str),
 }
@@ -98,11 +162,11 @@ impl<'a> Path<'a> {
         }
     }
 
-    fn last_cave(&self) -> &Cave {
+    fn last_cave(&self) -> &Cave<'_> {
         self.caves.last().unwrap()
     }
 
-    fn caves(&self) -> impl Iterator<Item = &Cave> {
+    fn caves(&self) -> impl Iterator<Item = &Cave<'_>> {
         self.caves.iter()
     }
 
@@ -129,10 +193,7 @@ impl<'a> Path<'a> {
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const SMALL_SAMPLE_INPUT: &str = include_str!("small_sample_input.txt");
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
@@ -141,27 +202,65 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(SMALL_SAMPLE_INPUT), 10);
-        assert_eq!(part_1(SAMPLE_INPUT), 19);
-        assert_eq!(part_1(LARGE_SAMPLE_INPUT), 226);
-        assert_eq!(part_1(INPUT), 4_304);
+        assert_eq!(
+            part_1(&build_connections_map(SMALL_SAMPLE_INPUT).unwrap()).unwrap(),
+            10
+        );
+        assert_eq!(
+            part_1(&build_connections_map(SAMPLE_INPUT).unwrap()).unwrap(),
+            19
+        );
+        assert_eq!(
+            part_1(&build_connections_map(LARGE_SAMPLE_INPUT).unwrap()).unwrap(),
+            226
+        );
+        assert_eq!(
+            part_1(&build_connections_map(INPUT).unwrap()).unwrap(),
+            4_304
+        );
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(SMALL_SAMPLE_INPUT), 36);
-        assert_eq!(part_2(SAMPLE_INPUT), 103);
-        assert_eq!(part_2(LARGE_SAMPLE_INPUT), 3509);
-        assert_eq!(part_2(INPUT), 118_242);
+        assert_eq!(
+            part_2(&build_connections_map(SMALL_SAMPLE_INPUT).unwrap()).unwrap(),
+            36
+        );
+        assert_eq!(
+            part_2(&build_connections_map(SAMPLE_INPUT).unwrap()).unwrap(),
+            103
+        );
+        assert_eq!(
+            part_2(&build_connections_map(LARGE_SAMPLE_INPUT).unwrap()).unwrap(),
+            3509
+        );
+        assert_eq!(
+            part_2(&build_connections_map(INPUT).unwrap()).unwrap(),
+            118_242
+        );
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT));
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            build_connections_map("start-A\nA-c1\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 3: expected a cave name, found `c1`"
+        );
+        assert_eq!(
+            build_connections_map("a-b\nb-end\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 6: expected a connection to the `start` cave, found nothing"
+        );
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT));
+    #[test]
+    fn test_no_start() {
+        let (a, b) = (Cave::Small("a"), Cave::Small("b"));
+        let connections = HashMap::from([(a, vec![b]), (b, vec![a])]);
+        assert!(matches!(part_1(&connections), Err(CaveError::NoStart)));
+        assert!(matches!(part_2(&connections), Err(CaveError::NoStart)));
     }
 }
@@ -0,0 +1,65 @@
+use aoc_core::Rng;
+use std::{collections::HashSet, fmt::Write};
+
+// Like the puzzle input, the code ends up 40 dots wide and 6 high
+const NUM_COLUMNS: usize = 40;
+const NUM_ROWS: usize = 6;
+const FOLDS: [char; 12] = ['x', 'y', 'x', 'y', 'x', 'y', 'x', 'y', 'x', 'y', 'y', 'y'];
+
+/// Transparent paper with (at most) `size` dots, and folds along twelve lines.
+///
+/// The paper is made by unfolding a 40 by 6 sheet, with every dot landing
+/// on either side of each fold, so no dot is ever on a fold line.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // From the last fold to the first
+    let mut folds = Vec::with_capacity(FOLDS.len());
+    let (mut num_columns, mut num_rows) = (NUM_COLUMNS, NUM_ROWS);
+    for &axis in FOLDS.iter().rev() {
+        let length = if axis == 'x' {
+            &mut num_columns
+        } else {
+            &mut num_rows
+        };
+        folds.push((axis, *length));
+        *length = 2 * *length + 1;
+    }
+
+    let size = size.min((NUM_COLUMNS * NUM_ROWS) << FOLDS.len());
+    let mut dots = HashSet::with_capacity(size);
+    let mut input = String::with_capacity(10 * size);
+    while dots.len() < size {
+        let mut dot = (rng.below(NUM_COLUMNS), rng.below(NUM_ROWS));
+        for &(axis, line) in &folds {
+            let coordinate = if axis == 'x' { &mut dot.0 } else { &mut dot.1 };
+            if rng.chance(1, 2) {
+                *coordinate = 2 * line - *coordinate;
+            }
+        }
+        if dots.insert(dot) {
+            writeln!(input, "{},{}", dot.0, dot.1).expect("writing to a String can't fail");
+        }
+    }
+
+    input.push('\n');
+    for (axis, line) in folds.iter().rev() {
+        writeln!(input, "fold along {axis}={line}").expect("writing to a String can't fail");
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day13;
+    use aoc_core::{Answer, Part, Solution};
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 800);
+        assert!(input.ends_with("fold along y=13\nfold along y=6\n"));
+        assert!(matches!(
+            Day13::solve(&input, Part::Two),
+            Ok(Answer::Text(code)) if code.lines().count() == NUM_ROWS
+        ));
+    }
+}
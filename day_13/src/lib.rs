@@ -1,47 +1,83 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
+
+mod generate;
+
+use aoc_core::{parse_lines, parse_token, Answer, ParseError, Solution, SolveError};
+use aoc_grid::Grid;
+pub use generate::generate;
 
 use std::{
+    cmp::Ordering,
     collections::HashSet,
+    error::Error,
     fmt::{Display, Formatter, Result as FormatResult},
-    num::ParseIntError,
-    ops::Index,
     str::FromStr,
 };
 
-fn part_1(input: &str) -> Result<usize, ParseError> {
-    let (coordinates, fold_alongs) = parse_input(input)?;
-    let mut grid: Grid = coordinates.into();
-    grid.fold(fold_alongs[0]);
-    Ok(grid.num_points())
+// Part 2 draws the folded paper, which is only readable when it's small
+const MAX_DRAWN: usize = 1 << 20;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = (Vec<Coordinate>, Vec<FoldAlong>);
+    type Error = SolveError<PaperError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(input).map_err(SolveError::Solve)?.into())
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(input).map_err(SolveError::Solve)?.into())
+    }
 }
 
-fn part_2(input: &str) -> Result<String, ParseError> {
-    let (coordinates, fold_alongs) = parse_input(input)?;
-    let mut grid: Grid = coordinates.into();
-    for fold_along in fold_alongs {
-        grid.fold(fold_along);
+fn part_1(
+    (coordinates, fold_alongs): &(Vec<Coordinate>, Vec<FoldAlong>),
+) -> Result<usize, PaperError> {
+    let &fold_along = fold_alongs.first().ok_or(PaperError::NoFolds)?;
+    let mut paper = Paper::from(coordinates.as_slice());
+    paper.fold(fold_along);
+    Ok(paper.num_points())
+}
+
+fn part_2(
+    (coordinates, fold_alongs): &(Vec<Coordinate>, Vec<FoldAlong>),
+) -> Result<String, PaperError> {
+    let mut paper = Paper::from(coordinates.as_slice());
+    for &fold_along in fold_alongs {
+        paper.fold(fold_along);
     }
-    Ok(format!("{}", grid))
+    paper.draw()
 }
 
 fn parse_input(input: &str) -> Result<(Vec<Coordinate>, Vec<FoldAlong>), ParseError> {
-    let (coordinates, fold_alongs) = input.split_once("\n\n").ok_or(ParseError)?;
-    Ok((
-        coordinates
-            .lines()
-            .map(str::parse)
-            .collect::<Result<_, _>>()?,
-        fold_alongs
-            .lines()
-            .map(str::parse)
-            .collect::<Result<_, _>>()?,
-    ))
+    let (coordinates, fold_alongs) = input.split_once("\n\n").ok_or_else(|| {
+        let end = input.trim_end().len();
+        ParseError::at(input, &input[end..end], "a blank line before the folds")
+    })?;
+    let coordinates =
+        parse_lines(coordinates, str::parse).map_err(|error| error.within(input, coordinates))?;
+    let fold_alongs: Vec<_> =
+        parse_lines(fold_alongs, str::parse).map_err(|error| error.within(input, fold_alongs))?;
+    if fold_alongs.is_empty() {
+        let end = input.trim_end().len();
+        return Err(ParseError::at(
+            input,
+            &input[end..end],
+            "a fold instruction",
+        ));
+    }
+    Ok((coordinates, fold_alongs))
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Coordinate {
+pub struct Coordinate {
     x: usize,
     y: usize,
 }
@@ -50,16 +86,18 @@ impl FromStr for Coordinate {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').ok_or(ParseError)?;
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::at(s, s, "a coordinate like `6,10`"))?;
         Ok(Self {
-            x: x.parse()?,
-            y: y.parse()?,
+            x: parse_token(s, x, "an x coordinate")?,
+            y: parse_token(s, y, "a y coordinate")?,
         })
     }
 }
 
 #[derive(Clone, Copy, Debug)]
-enum FoldAlong {
+pub enum FoldAlong {
     X(usize),
     Y(usize),
 }
@@ -68,138 +106,181 @@ impl FromStr for FoldAlong {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.strip_prefix("fold along ").ok_or(ParseError)?;
-        match s.split_once('=').ok_or(ParseError)? {
-            ("x", x) => Ok(Self::X(x.parse()?)),
-            ("y", y) => Ok(Self::Y(y.parse()?)),
-            _ => Err(ParseError),
+        let line = s;
+        let expected = "a fold like `fold along y=7`";
+        let s = s
+            .strip_prefix("fold along ")
+            .ok_or_else(|| ParseError::at(line, line, expected))?;
+        match s.split_once('=') {
+            Some(("x", x)) => Ok(Self::X(parse_token(line, x, "an x coordinate")?)),
+            Some(("y", y)) => Ok(Self::Y(parse_token(line, y, "a y coordinate")?)),
+            _ => Err(ParseError::at(line, s, "`x=` or `y=`")),
         }
     }
 }
 
-#[derive(Debug)]
-struct ParseError;
-
-impl From<ParseIntError> for ParseError {
-    fn from(_: ParseIntError) -> Self {
-        Self
-    }
-}
-
-struct Grid {
-    num_columns: usize,
+// Only the dots are kept, since the paper can be much larger than the
+// number of dots on it
+struct Paper {
+    dots: HashSet<Coordinate>,
     num_rows: usize,
-    points: Vec<Coordinate>,
+    num_columns: usize,
 }
 
-impl Grid {
+impl Paper {
+    // Everything past the fold line lands on its mirror image, and the fold
+    // line itself disappears, like anything mirrored past the edge
     fn fold(&mut self, fold_along: FoldAlong) {
+        let mirror = |coordinate: usize, line: usize| match coordinate.cmp(&line) {
+            Ordering::Less => Some(coordinate),
+            Ordering::Equal => None,
+            Ordering::Greater => line.checked_sub(coordinate - line),
+        };
+        self.dots = self
+            .dots
+            .iter()
+            .filter_map(|&Coordinate { x, y }| match fold_along {
+                FoldAlong::X(line) => Some(Coordinate {
+                    x: mirror(x, line)?,
+                    y,
+                }),
+                FoldAlong::Y(line) => Some(Coordinate {
+                    x,
+                    y: mirror(y, line)?,
+                }),
+            })
+            .collect();
+        // Folding along a line past the edge doesn't make the paper larger
         match fold_along {
-            FoldAlong::X(x_fold) => {
-                for point in &mut self.points {
-                    if point.x > x_fold {
-                        point.x = x_fold - (point.x - x_fold);
-                    }
-                }
-                self.num_columns = x_fold;
-            }
-            FoldAlong::Y(y_fold) => {
-                for point in &mut self.points {
-                    if point.y > y_fold {
-                        point.y = y_fold - (point.y - y_fold);
-                    }
-                    self.num_rows = y_fold;
-                }
-            }
+            FoldAlong::X(line) => self.num_columns = self.num_columns.min(line),
+            FoldAlong::Y(line) => self.num_rows = self.num_rows.min(line),
         }
     }
 
     fn num_points(&self) -> usize {
-        self.points.iter().copied().collect::<HashSet<_>>().len()
+        self.dots.len()
+    }
+
+    fn draw(&self) -> Result<String, PaperError> {
+        if self.num_rows.saturating_mul(self.num_columns) > MAX_DRAWN {
+            return Err(PaperError::TooLarge {
+                num_rows: self.num_rows,
+                num_columns: self.num_columns,
+            });
+        }
+        let mut paper = Grid::new(self.num_rows, self.num_columns, '.');
+        for &Coordinate { x, y } in &self.dots {
+            paper[(y, x)] = '#';
+        }
+        Ok(paper.to_string())
     }
 }
 
-impl From<Vec<Coordinate>> for Grid {
-    fn from(points: Vec<Coordinate>) -> Self {
-        Self {
-            num_columns: points
-                .iter()
-                .map(|coordinate| coordinate.x)
-                .max()
-                .unwrap_or_default()
-                + 1,
-            num_rows: points
+impl From<&[Coordinate]> for Paper {
+    fn from(points: &[Coordinate]) -> Self {
+        let extent = |coordinate: fn(&Coordinate) -> usize| {
+            points
                 .iter()
-                .map(|coordinate| coordinate.y)
+                .map(|point| coordinate(point).saturating_add(1))
                 .max()
                 .unwrap_or_default()
-                + 1,
-            points,
+        };
+        Self {
+            dots: points.iter().copied().collect(),
+            num_rows: extent(|point| point.y),
+            num_columns: extent(|point| point.x),
         }
     }
 }
 
-impl Index<Coordinate> for Grid {
-    type Output = bool;
-
-    fn index(&self, coordinate: Coordinate) -> &Self::Output {
-        if self.points.contains(&coordinate) {
-            &true
-        } else {
-            &false
-        }
-    }
+#[derive(Debug)]
+pub enum PaperError {
+    /// Part 1 counts the dots after the first fold, so there has to be one.
+    NoFolds,
+    /// The folded paper is too large to draw.
+    TooLarge { num_rows: usize, num_columns: usize },
 }
 
-impl Display for Grid {
+impl Display for PaperError {
     fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
-        for y in 0..self.num_rows {
-            for x in 0..self.num_columns {
-                if self[Coordinate { x, y }] {
-                    write!(formatter, "#")?;
-                } else {
-                    write!(formatter, ".")?;
-                }
-            }
-            writeln!(formatter);
+        match self {
+            Self::NoFolds => write!(formatter, "there are no fold instructions"),
+            Self::TooLarge {
+                num_rows,
+                num_columns,
+            } => write!(
+                formatter,
+                "the folded paper is {num_columns} by {num_rows}, too large to draw"
+            ),
         }
-
-        Ok(())
     }
 }
 
+impl Error for PaperError {}
+
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(SAMPLE_INPUT).unwrap(), 17);
-        assert_eq!(part_1(INPUT).unwrap(), 687);
+        assert_eq!(part_1(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(), 17);
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()).unwrap(), 687);
+        assert_eq!(
+            part_1(&(vec![Coordinate { x: 1, y: 1 }], vec![]))
+                .unwrap_err()
+                .to_string(),
+            "there are no fold instructions"
+        );
     }
 
     #[test]
     fn test_part_2() {
         assert_eq!(
-            part_2(SAMPLE_INPUT).unwrap(),
+            part_2(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(),
             include_str!("sample_output.txt")
         );
-        assert_eq!(part_2(INPUT).unwrap(), include_str!("output.txt"));
+        assert_eq!(
+            part_2(&parse_input(INPUT).unwrap()).unwrap(),
+            include_str!("output.txt")
+        );
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT).unwrap());
+    #[test]
+    fn test_large_paper() {
+        let input = parse_input("1000000,1000000\n\nfold along x=2\n").unwrap();
+        assert_eq!(part_1(&input).unwrap(), 0);
+        assert_eq!(
+            part_2(&input).unwrap_err().to_string(),
+            "the folded paper is 2 by 1000001, too large to draw"
+        );
+
+        // Folding past the edge leaves the paper as it is
+        let input = parse_input("1,1\n\nfold along x=5000000000\nfold along y=1\n").unwrap();
+        assert_eq!(part_1(&input).unwrap(), 1);
+        assert_eq!(part_2(&input).unwrap(), "..\n");
+
+        let input = parse_input("0,0\n3,0\n4,0\n\nfold along x=1\n").unwrap();
+        assert_eq!(part_2(&input).unwrap(), "#\n");
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT).unwrap());
+    #[test]
+    fn test_parse_errors() {
+        let parse_error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            parse_error("6,10\n0,14\n\nfold along y=7\nfold along z=5\n"),
+            "line 5, column 12: expected `x=` or `y=`, found `z=5`"
+        );
+        assert_eq!(
+            parse_error("6,10\n0,-14\n\nfold along y=7\n"),
+            "line 2, column 3: expected a y coordinate, found `-14`"
+        );
+        assert_eq!(
+            parse_error("6,10\n0,14\n\n"),
+            "line 2, column 5: expected a fold instruction, found nothing"
+        );
     }
 }
@@ -0,0 +1,33 @@
+use aoc_core::Rng;
+use std::fmt::Write;
+
+const NUM_ELEMENTS: usize = 10;
+
+/// A polymer template of `size` elements (but at least two), and a rule for
+/// every pair of the ten elements it's made of.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut elements: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut elements);
+    elements.truncate(NUM_ELEMENTS);
+
+    let mut input: String = (0..size.max(2)).map(|_| *rng.choose(&elements)).collect();
+    input.push_str("\n\n");
+    for &first in &elements {
+        for &second in &elements {
+            let insert = rng.choose(&elements);
+            writeln!(input, "{first}{second} -> {insert}").expect("writing to a String can't fail");
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 20);
+        assert_eq!(input.lines().count(), 2 + NUM_ELEMENTS * NUM_ELEMENTS);
+    }
+}
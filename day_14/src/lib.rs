@@ -1,44 +1,97 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
 
-use std::{collections::HashMap, iter::IntoIterator, str::FromStr};
+mod generate;
 
-fn part_1(input: &str) -> Result<usize, ParseError> {
-    let (polymer, pair_insertion_rules) = parse_input(input)?;
-    Ok(calculate_result(&polymer, &pair_insertion_rules, 10))
+use aoc_core::{parse_lines, Answer, ParseError, Solution, SolveError};
+pub use generate::generate;
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+    iter::IntoIterator,
+    str::FromStr,
+};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = (Polymer, PairInsertionRules);
+    type Error = SolveError<PolymerError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(input).map_err(SolveError::Solve)?.into())
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(input).map_err(SolveError::Solve)?.into())
+    }
 }
 
-fn part_2(input: &str) -> Result<usize, ParseError> {
-    let (polymer, pair_insertion_rules) = parse_input(input)?;
-    Ok(calculate_result(&polymer, &pair_insertion_rules, 40))
+fn part_1(
+    (polymer, pair_insertion_rules): &(Polymer, PairInsertionRules),
+) -> Result<usize, PolymerError> {
+    calculate_result(polymer, pair_insertion_rules, 10)
 }
 
-fn parse_input(input: &str) -> Result<(Polymer, PairInsertionRules), ParseError> {
-    let (polymer_template, pair_insertion_rules) = input.split_once("\n\n").ok_or(ParseError)?;
-    let pair_insertion_rules = pair_insertion_rules.lines().try_fold(
-        HashMap::with_capacity(pair_insertion_rules.len()),
-        |mut map, line| {
-            let (pair, to_insert) = line.split_once(" -> ").ok_or(ParseError)?;
-            map.insert(parse_byte_array(pair)?, parse_byte(to_insert)?);
-            Ok(map)
-        },
-    )?;
-
-    Ok((polymer_template.parse()?, pair_insertion_rules))
+fn part_2(
+    (polymer, pair_insertion_rules): &(Polymer, PairInsertionRules),
+) -> Result<usize, PolymerError> {
+    calculate_result(polymer, pair_insertion_rules, 40)
 }
 
-fn parse_byte_array(input: &str) -> Result<[u8; 2], ParseError> {
-    let mut pair_bytes = input.bytes();
-    let mut array = [0; 2];
-    for byte in &mut array {
-        *byte = pair_bytes.next().ok_or(ParseError)?;
+fn parse_input(input: &str) -> Result<(Polymer, PairInsertionRules), ParseError> {
+    let (polymer_template, rules) = input.split_once("\n\n").ok_or_else(|| {
+        let end = input.trim_end().len();
+        ParseError::at(
+            input,
+            &input[end..end],
+            "a blank line before the pair insertion rules",
+        )
+    })?;
+    let pair_insertion_rules: PairInsertionRules = parse_lines(rules, |line| {
+        let (pair, to_insert) = line
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::at(line, line, "a rule like `CH -> B`"))?;
+        let [to_insert] = parse_elements(line, to_insert, "one element")?;
+        Ok((parse_elements(line, pair, "a pair of elements")?, to_insert))
+    })
+    .map_err(|error| error.within(input, rules))?
+    .into_iter()
+    .collect();
+    let polymer: Polymer = polymer_template
+        .parse()
+        .map_err(|error: ParseError| error.within(input, polymer_template))?;
+    if let Some(index) = polymer
+        .0
+        .windows(2)
+        .position(|pair| !pair_insertion_rules.contains_key(pair))
+    {
+        return Err(ParseError::at(
+            input,
+            &polymer_template[index..index + 2],
+            "a pair of elements with an insertion rule",
+        ));
     }
-    Ok(array)
+
+    Ok((polymer, pair_insertion_rules))
 }
 
-fn parse_byte(input: &str) -> Result<u8, ParseError> {
-    input.bytes().next().ok_or(ParseError)
+// Elements are uppercase letters
+fn parse_elements<const N: usize>(
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> Result<[u8; N], ParseError> {
+    <[u8; N]>::try_from(token.as_bytes())
+        .ok()
+        .filter(|elements| elements.iter().all(u8::is_ascii_uppercase))
+        .ok_or_else(|| ParseError::at(line, token, expected))
 }
 
 type ElementCounts = HashMap<u8, usize>;
@@ -48,24 +101,23 @@ fn calculate_result(
     polymer: &Polymer,
     pair_insertion_rules: &PairInsertionRules,
     steps: usize,
-) -> usize {
+) -> Result<usize, PolymerError> {
     let mut cache: ElementCountCache = HashMap::new();
-    let mut counts = polymer
-        .0
-        .windows(2)
-        .map(|pair| count_elements([pair[0], pair[1]], steps, pair_insertion_rules, &mut cache))
-        .flat_map(IntoIterator::into_iter)
-        .fold(HashMap::new(), |mut counts, (element, count)| {
+    let mut counts = HashMap::new();
+    for pair in polymer.0.windows(2) {
+        let pair_counts =
+            count_elements([pair[0], pair[1]], steps, pair_insertion_rules, &mut cache)?;
+        for (element, count) in pair_counts {
             *counts.entry(element).or_insert(0) += count;
-            counts
-        });
+        }
+    }
 
     // Subtract double counted elements.
     for element in polymer.0.iter().skip(1).take(polymer.0.len() - 2) {
         *counts.get_mut(element).unwrap() -= 1;
     }
 
-    counts.values().max().unwrap() - counts.values().min().unwrap()
+    Ok(counts.values().max().unwrap() - counts.values().min().unwrap())
 }
 
 fn count_elements(
@@ -73,9 +125,9 @@ fn count_elements(
     steps: usize,
     pair_insertion_rules: &PairInsertionRules,
     cache: &mut ElementCountCache,
-) -> ElementCounts {
+) -> Result<ElementCounts, PolymerError> {
     if let Some(result) = cache.get(&(pair, steps)) {
-        result.clone()
+        Ok(result.clone())
     } else if steps == 0 {
         let result: ElementCounts = pair
             .into_iter()
@@ -84,67 +136,154 @@ fn count_elements(
                 result
             });
         cache.insert((pair, steps), result.clone());
-        result
+        Ok(result)
     } else {
-        let insert = *pair_insertion_rules.get(&pair).unwrap();
-        let mut result = [[pair[0], insert], [insert, pair[1]]]
-            .into_iter()
-            .map(|pair| count_elements(pair, steps - 1, pair_insertion_rules, cache))
-            .flat_map(IntoIterator::into_iter)
-            .fold(HashMap::new(), |mut counts, (element, count)| {
-                *counts.entry(element).or_insert(0) += count;
-                counts
-            });
+        let insert = *pair_insertion_rules
+            .get(&pair)
+            .ok_or(PolymerError::NoRule { pair })?;
+        let mut result = HashMap::new();
+        for pair in [[pair[0], insert], [insert, pair[1]]] {
+            for (element, count) in count_elements(pair, steps - 1, pair_insertion_rules, cache)? {
+                *result.entry(element).or_insert(0) += count;
+            }
+        }
         *result.get_mut(&insert).unwrap() -= 1;
         cache.insert((pair, steps), result.clone());
-        result
+        Ok(result)
     }
 }
 
-struct Polymer(Vec<u8>);
+#[derive(Debug)]
+pub struct Polymer(Vec<u8>);
 
 impl FromStr for Polymer {
     type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if let Some((index, element)) = input
+            .char_indices()
+            .find(|(_, element)| !element.is_ascii_uppercase())
+        {
+            let token = &input[index..index + element.len_utf8()];
+            return Err(ParseError::at(input, token, "an element"));
+        }
+        if input.len() < 2 {
+            return Err(ParseError::at(input, input, "at least two elements"));
+        }
         Ok(Self(input.bytes().collect()))
     }
 }
 
-type PairInsertionRules = HashMap<[u8; 2], u8>;
+pub type PairInsertionRules = HashMap<[u8; 2], u8>;
 
 #[derive(Debug)]
-struct ParseError;
+pub enum PolymerError {
+    /// The polymer grew a pair that no rule says what to insert between.
+    NoRule { pair: [u8; 2] },
+}
+
+impl Display for PolymerError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::NoRule { pair } => write!(
+                formatter,
+                "there is no rule for the pair `{}{}`",
+                char::from(pair[0]),
+                char::from(pair[1])
+            ),
+        }
+    }
+}
+
+impl Error for PolymerError {}
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
+    use aoc_core::Rng;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(SAMPLE_INPUT).unwrap(), 1_588);
-        assert_eq!(part_1(INPUT).unwrap(), 2_602);
+        assert_eq!(part_1(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(), 1_588);
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()).unwrap(), 2_602);
+        // Only the template's pairs need rules, not the ones they insert
+        assert!(matches!(
+            part_1(&parse_input("NN\n\nNN -> C\n").unwrap()),
+            Err(PolymerError::NoRule { pair: [b'N', b'C'] })
+        ));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(SAMPLE_INPUT).unwrap(), 2_188_189_693_529);
-        assert_eq!(part_2(INPUT).unwrap(), 2_942_885_922_173);
+        assert_eq!(
+            part_2(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(),
+            2_188_189_693_529
+        );
+        assert_eq!(
+            part_2(&parse_input(INPUT).unwrap()).unwrap(),
+            2_942_885_922_173
+        );
     }
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT).unwrap());
+    #[test]
+    fn test_parse_errors() {
+        let parse_error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            parse_error("NNCB\n\nCH -> B\nHH -> n\n"),
+            "line 4, column 7: expected one element, found `n`"
+        );
+        assert_eq!(
+            parse_error("NN-B\n\nCH -> B\n"),
+            "line 1, column 3: expected an element, found `-`"
+        );
+        assert_eq!(
+            parse_error("NNCB\nCH -> B\n"),
+            "line 2, column 8: expected a blank line before the pair insertion rules, found nothing"
+        );
+        assert_eq!(
+            parse_error("NN\n\n"),
+            "line 1, column 1: expected a pair of elements with an insertion rule, found `NN`"
+        );
+        assert_eq!(
+            parse_error("NCB\n\nNC -> B\n"),
+            "line 1, column 2: expected a pair of elements with an insertion rule, found `CB`"
+        );
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT).unwrap());
+    // Builds the whole polymer
+    fn part_1_naively(input: &str) -> usize {
+        let (template, rules) = input.split_once("\n\n").unwrap();
+        let rules: HashMap<&[u8], u8> = rules
+            .lines()
+            .map(|rule| (&rule.as_bytes()[..2], rule.as_bytes()[6]))
+            .collect();
+        let mut polymer = template.as_bytes().to_vec();
+        for _ in 0..10 {
+            let mut next = vec![polymer[0]];
+            for pair in polymer.windows(2) {
+                next.extend(rules.get(pair));
+                next.push(pair[1]);
+            }
+            polymer = next;
+        }
+        let mut counts = HashMap::new();
+        for element in polymer {
+            *counts.entry(element).or_insert(0) += 1;
+        }
+        counts.values().max().unwrap() - counts.values().min().unwrap()
+    }
+
+    #[test]
+    fn test_against_naive() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 8);
+            assert_eq!(
+                part_1(&parse_input(&input).unwrap()).unwrap(),
+                part_1_naively(&input)
+            );
+        }
     }
 }
@@ -0,0 +1,11 @@
+use aoc_core::Rng;
+
+/// A `size` by `size` map of risk levels.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::with_capacity((size + 1) * size);
+    for _ in 0..size {
+        input.extend((0..size).map(|_| char::from(*rng.choose(b"123456789"))));
+        input.push('\n');
+    }
+    input
+}
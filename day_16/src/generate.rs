@@ -0,0 +1,131 @@
+use aoc_core::Rng;
+use std::{collections::BTreeSet, fmt::Write, mem};
+
+// Keeps every value within 64 bits, sums and products included
+const MAX_VALUE: usize = 1 << 48;
+const MAX_SUB_PACKETS: usize = 10;
+
+/// A hexadecimal BITS transmission of `size` packets (but at least one),
+/// nested in each other.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut bits = String::new();
+    write_packet(rng, size.max(1), MAX_VALUE, &mut bits);
+    while !bits.len().is_multiple_of(4) {
+        bits.push('0');
+    }
+
+    let mut input: String = bits
+        .as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let digit = nibble
+                .iter()
+                .fold(0, |digit, &bit| 2 * digit + usize::from(bit - b'0'));
+            char::from(b"0123456789ABCDEF"[digit])
+        })
+        .collect();
+    input.push('\n');
+    input
+}
+
+// `size` packets, counting this one and all of its sub-packets, with a value
+// of at most `max_value`
+fn write_packet(rng: &mut Rng, size: usize, max_value: usize, bits: &mut String) {
+    write_number(bits, rng.below(8), 3);
+    if size == 1 {
+        write_number(bits, 4, 3);
+        // Random magnitudes, rather than mostly huge numbers
+        let bit_length = usize::BITS - max_value.leading_zeros();
+        let max_literal = max_value >> rng.below(bit_length as usize + 1);
+        write_literal(bits, rng.range(0..=max_literal));
+        return;
+    }
+
+    // Comparisons need exactly two sub-packets
+    let type_id = loop {
+        let type_id = rng.below(8);
+        if type_id != 4 && (size > 2 || type_id < 4) {
+            break type_id;
+        }
+    };
+    let num_sub_packets = if type_id > 4 {
+        2
+    } else {
+        rng.range(1..=(size - 1).min(MAX_SUB_PACKETS))
+    };
+    let max_sub_value = match type_id {
+        0 => max_value / num_sub_packets,
+        1 => root(max_value, num_sub_packets),
+        _ => max_value,
+    };
+
+    let mut sub_packets = String::new();
+    for size in split(rng, size - 1, num_sub_packets) {
+        write_packet(rng, size, max_sub_value, &mut sub_packets);
+    }
+    write_number(bits, type_id, 3);
+    if sub_packets.len() < 1 << 15 && rng.chance(1, 2) {
+        write_number(bits, 0, 1);
+        write_number(bits, sub_packets.len(), 15);
+    } else {
+        write_number(bits, 1, 1);
+        write_number(bits, num_sub_packets, 11);
+    }
+    bits.push_str(&sub_packets);
+}
+
+fn write_number(bits: &mut String, number: usize, width: usize) {
+    write!(bits, "{number:0width$b}").expect("writing to a String can't fail");
+}
+
+// Groups of four bits, each with a 1 in front except for the last one
+fn write_literal(bits: &mut String, value: usize) {
+    let num_groups = (usize::BITS - value.leading_zeros()).div_ceil(4).max(1);
+    for group in (0..num_groups).rev() {
+        let prefix = usize::from(group > 0);
+        write_number(bits, prefix, 1);
+        write_number(bits, (value >> (4 * group)) & 0b1111, 4);
+    }
+}
+
+// The largest number whose `k`th power is at most `number`
+fn root(number: usize, k: usize) -> usize {
+    let k = u32::try_from(k).expect("a small power");
+    let (mut low, mut high) = (0, number + 1);
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if middle.checked_pow(k).is_some_and(|power| power <= number) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
+
+// `total` split into `num_parts` random parts, all at least 1
+fn split(rng: &mut Rng, total: usize, num_parts: usize) -> Vec<usize> {
+    let mut cuts = BTreeSet::new();
+    while cuts.len() < num_parts - 1 {
+        cuts.insert(rng.range(1..=total - 1));
+    }
+    cuts.into_iter()
+        .chain([total])
+        .scan(0, |previous, cut| Some(cut - mem::replace(previous, cut)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(root(1 << 48, 2), 1 << 24);
+        assert_eq!(root(26, 3), 2);
+        assert_eq!(root(0, 4), 0);
+        assert_eq!(split(&mut Rng::new(1), 5, 1), [5]);
+        assert_eq!(split(&mut Rng::new(1), 5, 5), [1; 5]);
+        assert_eq!(split(&mut Rng::new(1), 100, 7).iter().sum::<usize>(), 100);
+    }
+}
@@ -1,98 +1,180 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(int_roundings, test)]
 
-fn part_1(input: &str) -> Result<usize, ParseError> {
-    let bits = convert_hex_to_binary(input)?;
-    let (packet, remaining) = parse_packet(&bits)?;
-    if remaining.bytes().all(|c| c == b'0') {
-        Ok(packet.sum_versions())
-    } else {
-        Err(ParseError)
+mod generate;
+
+use aoc_core::{Answer, ParseError, Solution, SolveError};
+pub use generate::generate;
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+// Parsing recurses into sub-packets, so deeper nesting could overflow the stack
+pub const MAX_DEPTH: usize = 100;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Packet;
+    type Error = SolveError<PacketError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input)?)
     }
-}
 
-fn part_2(input: &str) -> Result<usize, ParseError> {
-    let bits = convert_hex_to_binary(input)?;
-    let (packet, remaining) = parse_packet(&bits)?;
-    if remaining.bytes().all(|c| c == b'0') {
-        Ok(packet.value())
-    } else {
-        Err(ParseError)
+    fn part_1(packet: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(packet).into())
+    }
+
+    fn part_2(packet: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(packet).map_err(SolveError::Solve)?.into())
     }
 }
 
-fn convert_hex_to_binary(hex: &str) -> Result<String, ParseError> {
-    hex.bytes()
-        .map(|hex_byte| match hex_byte {
-            b'0'..=b'9' => Ok(hex_byte - b'0'),
-            b'A'..=b'F' => Ok(10 + hex_byte - b'A'),
-            _ => Err(ParseError),
+fn part_1(packet: &Packet) -> usize {
+    packet.sum_versions()
+}
+
+fn part_2(packet: &Packet) -> Result<usize, PacketError> {
+    packet.value().ok_or(PacketError::Overflow)
+}
+
+fn parse_input(input: &str) -> Result<Packet, ParseError> {
+    let hex = input.trim_end();
+    let bits = convert_hex_to_binary(hex)?;
+    parse_packet(&bits, 0)
+        .and_then(|(packet, remaining)| {
+            if remaining.bytes().all(|c| c == b'0') {
+                Ok(packet)
+            } else {
+                Err(BitsError::new(
+                    remaining,
+                    "only zeros after the outermost packet",
+                ))
+            }
         })
-        .map(|binary_byte| binary_byte.map(|byte| format!("{:04b}", byte)))
-        .collect()
+        .map_err(|error| error.locate(hex, &bits))
 }
 
-fn parse_n_bits(n: usize, input: &str) -> Result<(&str, &str), ParseError> {
+fn convert_hex_to_binary(hex: &str) -> Result<String, ParseError> {
+    let mut bits = String::with_capacity(4 * hex.len());
+    for (index, hex_digit) in hex.char_indices() {
+        let digit = match hex_digit {
+            '0'..='9' | 'A'..='F' => hex_digit.to_digit(16).unwrap_or_default(),
+            _ => {
+                let token = &hex[index..index + hex_digit.len_utf8()];
+                return Err(ParseError::at(hex, token, "a hexadecimal digit"));
+            }
+        };
+        bits.extend(
+            (0..4)
+                .rev()
+                .map(|shift| if digit >> shift & 1 == 1 { '1' } else { '0' }),
+        );
+    }
+    Ok(bits)
+}
+
+// The packet parsers work on a string of bits, so they can't point into the
+// hexadecimal input themselves
+struct BitsError<'a> {
+    // Always a suffix of all bits, so its length gives the position
+    remaining: &'a str,
+    expected: String,
+}
+
+impl<'a> BitsError<'a> {
+    fn new(remaining: &'a str, expected: impl Into<String>) -> Self {
+        Self {
+            remaining,
+            expected: expected.into(),
+        }
+    }
+
+    fn locate(self, hex: &str, bits: &str) -> ParseError {
+        let bit = bits.len() - self.remaining.len();
+        let index = bit / 4;
+        ParseError::new(
+            1,
+            index + 1,
+            hex.get(index..=index).unwrap_or_default(),
+            format!("{} at bit {bit}", self.expected),
+        )
+    }
+}
+
+type BitsResult<'a, T> = Result<(T, &'a str), BitsError<'a>>;
+
+fn parse_n_bits(n: usize, input: &str) -> BitsResult<'_, &str> {
     if input.len() >= n {
         Ok((&input[0..n], &input[n..]))
     } else {
-        Err(ParseError)
+        Err(BitsError::new(input, format!("a {n} bit field")))
     }
 }
 
-fn parse_n_bit_number(n: usize, input: &str) -> Result<(usize, &str), ParseError> {
+fn parse_n_bit_number(n: usize, input: &str) -> BitsResult<'_, usize> {
     parse_n_bits(n, input).and_then(|(bits, remaining)| {
-        let number = usize::from_str_radix(bits, 2).map_err(|_| ParseError)?;
+        let number = usize::from_str_radix(bits, 2)
+            .map_err(|_| BitsError::new(input, format!("a {n} bit number")))?;
         Ok((number, remaining))
     })
 }
 
-fn parse_packet(input: &str) -> Result<(Packet, &str), ParseError> {
+fn parse_packet(input: &str, depth: usize) -> BitsResult<'_, Packet> {
     let (version, remaining) = parse_n_bit_number(3, input)?;
     let (operation_type, remaining) = parse_operation_type(remaining)?;
     if let OperationType::SingleNumber = operation_type {
         let (value, remaining) = parse_value(remaining)?;
-        Ok((Packet::Literal { version, value }, remaining))
+        return Ok((Packet::Literal { version, value }, remaining));
+    }
+
+    if depth == MAX_DEPTH {
+        return Err(BitsError::new(
+            input,
+            format!("a literal value packet (packets nest at most {MAX_DEPTH} deep)"),
+        ));
+    }
+    let (length_type_id, remaining) = parse_n_bit_number(1, remaining)?;
+    let (packets, remaining) = if length_type_id == 0 {
+        let (length, remaining) = parse_n_bit_number(15, remaining)?;
+        parse_length_of_packets(length, remaining, depth + 1)?
     } else {
-        let (length_type_id, remaining) = parse_n_bit_number(1, remaining)?;
-        match length_type_id {
-            0 => {
-                let (length, remaining) = parse_n_bit_number(15, remaining)?;
-                let (packets, remaining) = parse_length_of_packets(length, remaining)?;
-                Ok((
-                    Packet::Operation {
-                        version,
-                        operation_type,
-                        packets,
-                    },
-                    remaining,
-                ))
-            }
-            1 => {
-                let (n, remaining) = parse_n_bit_number(11, remaining)?;
-                let (packets, remaining) = parse_n_packets(n, remaining)?;
-                Ok((
-                    Packet::Operation {
-                        version,
-                        operation_type,
-                        packets,
-                    },
-                    remaining,
-                ))
-            }
-            _ => Err(ParseError),
-        }
+        let (n, remaining) = parse_n_bit_number(11, remaining)?;
+        parse_n_packets(n, remaining, depth + 1)?
+    };
+    match (&operation_type, packets.len()) {
+        (OperationType::GreaterThan | OperationType::LessThan | OperationType::EqualTo, 2)
+        | (OperationType::Sum | OperationType::Product, _)
+        | (OperationType::Minimum | OperationType::Maximum, 1..) => Ok((
+            Packet::Operation {
+                version,
+                operation_type,
+                packets,
+            },
+            remaining,
+        )),
+        (OperationType::Minimum | OperationType::Maximum, _) => Err(BitsError::new(
+            input,
+            "a minimum or maximum packet with sub-packets",
+        )),
+        _ => Err(BitsError::new(
+            input,
+            "a comparison packet with two sub-packets",
+        )),
     }
 }
 
-fn parse_operation_type(input: &str) -> Result<(OperationType, &str), ParseError> {
+fn parse_operation_type(input: &str) -> BitsResult<'_, OperationType> {
     let (type_id, remaining) = parse_n_bit_number(3, input)?;
-    let operation_type = OperationType::try_from(type_id)?;
+    let operation_type =
+        OperationType::try_from(type_id).map_err(|_| BitsError::new(input, "a packet type ID"))?;
     Ok((operation_type, remaining))
 }
 
-fn parse_value(input: &str) -> Result<(usize, &str), ParseError> {
+fn parse_value(input: &str) -> BitsResult<'_, usize> {
     let mut number = String::new();
     let mut is_last = false;
     let mut remaining = input;
@@ -103,18 +185,21 @@ fn parse_value(input: &str) -> Result<(usize, &str), ParseError> {
         number.push_str(group);
         remaining = r;
     }
-    Ok((
-        usize::from_str_radix(&number, 2).map_err(|_| ParseError)?,
-        remaining,
-    ))
+    let value = usize::from_str_radix(&number, 2)
+        .map_err(|_| BitsError::new(input, "a literal value that fits in 64 bits"))?;
+    Ok((value, remaining))
 }
 
-fn parse_length_of_packets(length: usize, input: &str) -> Result<(Vec<Packet>, &str), ParseError> {
+fn parse_length_of_packets(
+    length: usize,
+    input: &str,
+    depth: usize,
+) -> BitsResult<'_, Vec<Packet>> {
     let mut length_of_parsed_sub_packets = 0;
     let mut sub_packets: Vec<Packet> = Vec::new();
     let mut remaining = input;
     while length_of_parsed_sub_packets < length && !remaining.is_empty() {
-        let (sub_packet, r) = parse_packet(remaining)?;
+        let (sub_packet, r) = parse_packet(remaining, depth)?;
         sub_packets.push(sub_packet);
         length_of_parsed_sub_packets += remaining.len() - r.len();
         remaining = r;
@@ -122,18 +207,19 @@ fn parse_length_of_packets(length: usize, input: &str) -> Result<(Vec<Packet>, &
     Ok((sub_packets, remaining))
 }
 
-fn parse_n_packets(n: usize, input: &str) -> Result<(Vec<Packet>, &str), ParseError> {
+fn parse_n_packets(n: usize, input: &str, depth: usize) -> BitsResult<'_, Vec<Packet>> {
     let mut sub_packets = Vec::with_capacity(n);
     let mut r = input;
     for _ in 0..n {
-        let (sub_packet, remaining) = parse_packet(r)?;
+        let (sub_packet, remaining) = parse_packet(r, depth)?;
         sub_packets.push(sub_packet);
         r = remaining;
     }
     Ok((sub_packets, r))
 }
 
-enum Packet {
+#[derive(Debug)]
+pub enum Packet {
     Literal {
         version: usize,
         value: usize,
@@ -161,46 +247,63 @@ impl Packet {
         }
     }
 
-    fn value(&self) -> usize {
+    // None if a sum or product doesn't fit in a usize
+    fn value(&self) -> Option<usize> {
         match self {
-            Self::Literal { value, .. } => *value,
+            Self::Literal { value, .. } => Some(*value),
             Self::Operation {
                 packets,
                 operation_type,
                 ..
-            } => match operation_type {
-                OperationType::Sum => packets.iter().map(Packet::value).sum(),
-                OperationType::Product => packets.iter().map(Packet::value).product(),
-                OperationType::Minimum => packets.iter().map(Packet::value).min().unwrap(),
-                OperationType::Maximum => packets.iter().map(Packet::value).max().unwrap(),
-                OperationType::SingleNumber => panic!(),
-                OperationType::GreaterThan => {
-                    if packets[0].value() > packets[1].value() {
-                        1
-                    } else {
-                        0
+            } => {
+                let mut values = packets.iter().map(Packet::value);
+                match operation_type {
+                    OperationType::Sum => {
+                        values.try_fold(0_usize, |sum, value| sum.checked_add(value?))
                     }
-                }
-                OperationType::LessThan => {
-                    if packets[0].value() < packets[1].value() {
-                        1
-                    } else {
-                        0
+                    OperationType::Product => {
+                        values.try_fold(1_usize, |product, value| product.checked_mul(value?))
                     }
-                }
-                OperationType::EqualTo => {
-                    if packets[0].value() == packets[1].value() {
-                        1
-                    } else {
-                        0
+                    OperationType::Minimum => {
+                        values.try_fold(usize::MAX, |minimum, value| Some(minimum.min(value?)))
+                    }
+                    OperationType::Maximum => {
+                        values.try_fold(0, |maximum, value| Some(maximum.max(value?)))
+                    }
+                    OperationType::SingleNumber => panic!(),
+                    OperationType::GreaterThan => {
+                        Some(usize::from(packets[0].value()? > packets[1].value()?))
+                    }
+                    OperationType::LessThan => {
+                        Some(usize::from(packets[0].value()? < packets[1].value()?))
+                    }
+                    OperationType::EqualTo => {
+                        Some(usize::from(packets[0].value()? == packets[1].value()?))
                     }
                 }
-            },
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketError {
+    /// The value of the outermost packet doesn't fit in a `usize`.
+    Overflow,
+}
+
+impl Display for PacketError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Overflow => write!(formatter, "the value of the transmission is too large"),
         }
     }
 }
 
-enum OperationType {
+impl Error for PacketError {}
+
+#[derive(Debug)]
+pub enum OperationType {
     Sum,
     Product,
     Minimum,
@@ -212,7 +315,7 @@ enum OperationType {
 }
 
 impl TryFrom<usize> for OperationType {
-    type Error = ParseError;
+    type Error = usize;
 
     fn try_from(operation_type: usize) -> Result<Self, Self::Error> {
         match operation_type {
@@ -224,57 +327,109 @@ impl TryFrom<usize> for OperationType {
             5 => Ok(Self::GreaterThan),
             6 => Ok(Self::LessThan),
             7 => Ok(Self::EqualTo),
-            _ => Err(ParseError),
+            _ => Err(operation_type),
         }
     }
 }
 
-#[derive(Debug)]
-struct ParseError;
-
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const INPUT: &str = include_str!("input.txt");
 
+    fn to_hex(bits: &str) -> String {
+        bits.as_bytes()
+            .chunks(4)
+            .map(|nibble| {
+                let digit = nibble
+                    .iter()
+                    .fold(0, |digit, &bit| 2 * digit + u32::from(bit - b'0'));
+                char::from_digit(digit << (4 - nibble.len()), 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            })
+            .collect()
+    }
+
+    // Sums of a single sub-packet, nested `depth` deep around a literal 0
+    fn nested_sums(depth: usize) -> String {
+        to_hex(&("000000100000000001".repeat(depth) + "00010000000"))
+    }
+
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1("D2FE28").unwrap(), 6);
-        assert_eq!(part_1("38006F45291200").unwrap(), 9);
-        assert_eq!(part_1("EE00D40C823060").unwrap(), 14);
-        assert_eq!(part_1("8A004A801A8002F478").unwrap(), 16);
-        assert_eq!(part_1("620080001611562C8802118E34").unwrap(), 12);
-        assert_eq!(part_1("C0015000016115A2E0802F182340").unwrap(), 23);
-        assert_eq!(part_1("A0016C880162017C3686B18A3D4780").unwrap(), 31);
-
-        assert_eq!(part_1(INPUT).unwrap(), 889);
+        assert_eq!(part_1(&parse_input("D2FE28").unwrap()), 6);
+        assert_eq!(part_1(&parse_input("38006F45291200").unwrap()), 9);
+        assert_eq!(part_1(&parse_input("EE00D40C823060").unwrap()), 14);
+        assert_eq!(part_1(&parse_input("8A004A801A8002F478").unwrap()), 16);
+        assert_eq!(
+            part_1(&parse_input("620080001611562C8802118E34").unwrap()),
+            12
+        );
+        assert_eq!(
+            part_1(&parse_input("C0015000016115A2E0802F182340").unwrap()),
+            23
+        );
+        assert_eq!(
+            part_1(&parse_input("A0016C880162017C3686B18A3D4780").unwrap()),
+            31
+        );
+
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()), 889);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2("C200B40A82").unwrap(), 3);
-        assert_eq!(part_2("04005AC33890").unwrap(), 54);
-        assert_eq!(part_2("880086C3E88112").unwrap(), 7);
-        assert_eq!(part_2("CE00C43D881120").unwrap(), 9);
-        assert_eq!(part_2("D8005AC2A8F0").unwrap(), 1);
-        assert_eq!(part_2("F600BC2D8F").unwrap(), 0);
-        assert_eq!(part_2("9C005AC2F8F0").unwrap(), 0);
-        assert_eq!(part_2("9C0141080250320F1802104A08").unwrap(), 1);
-
-        assert_eq!(part_2(INPUT).unwrap(), 739_303_923_668);
-    }
+        assert_eq!(part_2(&parse_input("C200B40A82").unwrap()).unwrap(), 3);
+        assert_eq!(part_2(&parse_input("04005AC33890").unwrap()).unwrap(), 54);
+        assert_eq!(part_2(&parse_input("880086C3E88112").unwrap()).unwrap(), 7);
+        assert_eq!(part_2(&parse_input("CE00C43D881120").unwrap()).unwrap(), 9);
+        assert_eq!(part_2(&parse_input("D8005AC2A8F0").unwrap()).unwrap(), 1);
+        assert_eq!(part_2(&parse_input("F600BC2D8F").unwrap()).unwrap(), 0);
+        assert_eq!(part_2(&parse_input("9C005AC2F8F0").unwrap()).unwrap(), 0);
+        assert_eq!(
+            part_2(&parse_input("9C0141080250320F1802104A08").unwrap()).unwrap(),
+            1
+        );
 
-    #[bench]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT).unwrap());
+        assert_eq!(
+            part_2(&parse_input(INPUT).unwrap()).unwrap(),
+            739_303_923_668
+        );
+        assert_eq!(
+            part_2(&parse_input(&nested_sums(MAX_DEPTH)).unwrap()).unwrap(),
+            0
+        );
+
+        // A sum of two literals of 2^63
+        let literal = "000100".to_owned() + "11000" + &"10000".repeat(14) + "00000";
+        let sum = to_hex(&("000000100000000010".to_owned() + &literal + &literal));
+        assert_eq!(
+            part_2(&parse_input(&sum).unwrap()).unwrap_err().to_string(),
+            "the value of the transmission is too large"
+        );
     }
 
-    #[bench]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT).unwrap());
+    #[test]
+    fn test_parse_errors() {
+        let parse_error = |input: &str| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            parse_error("D2FE2g"),
+            "line 1, column 6: expected a hexadecimal digit, found `g`"
+        );
+        assert_eq!(
+            parse_error("D2FE"),
+            "line 1, column 5: expected a 1 bit field at bit 16, found nothing"
+        );
+        assert_eq!(
+            parse_error("D2FE28F"),
+            "line 1, column 6: expected only zeros after the outermost packet at bit 21, found `8`"
+        );
+        assert_eq!(
+            parse_error(&nested_sums(200_000)),
+            "line 1, column 451: expected a literal value packet (packets nest at most 100 deep) \
+             at bit 1800, found `0`"
+        );
     }
 }
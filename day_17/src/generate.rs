@@ -0,0 +1,13 @@
+use aoc_core::Rng;
+
+/// A target area about `size` steps to the right of and below the probe.
+///
+/// The puzzle input is about size 100, and the solvers only look for
+/// trajectories that reach about that far.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let x_min = rng.range(size..=2 * size);
+    let x_max = x_min + rng.range(size / 10..=size / 4);
+    let y_max = rng.range(size / 2..=3 * size / 4) + 1;
+    let y_min = y_max + rng.range(size / 10..=size / 4);
+    format!("target area: x={x_min}..{x_max}, y=-{y_min}..-{y_max}\n")
+}
@@ -1,26 +1,42 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(test)]
+
+mod generate;
+
+use aoc_core::{parse_token, Answer, ParseError, Solution};
+pub use generate::generate;
 
 use std::{
     cmp::{max, min},
-    num::ParseIntError,
+    ops::RangeInclusive,
     str::FromStr,
 };
 
-fn part_1(input: &str) -> Result<i32, ParseError> {
-    let target_area = TargetArea::from_str(input)?;
-    let max_height = find_max_height(&target_area);
-    Ok(max_height)
-}
+// Every launch velocity that could reach the target is tried, so it can't be
+// arbitrarily far away
+pub const MAX_DISTANCE: i32 = 1_000;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = TargetArea;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        input.parse()
+    }
 
-fn part_2(input: &str) -> Result<i32, ParseError> {
-    let target_area = TargetArea::from_str(input)?;
-    let num_trajectories = find_num_trajectories(&target_area);
-    Ok(num_trajectories)
+    fn part_1(target_area: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(find_max_height(target_area).into())
+    }
+
+    fn part_2(target_area: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(find_num_trajectories(target_area).into())
+    }
 }
 
-struct TargetArea {
+#[derive(Debug)]
+pub struct TargetArea {
     x_min: i32,
     x_max: i32,
     y_min: i32,
@@ -31,23 +47,42 @@ impl FromStr for TargetArea {
     type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let interesting_part = input.strip_prefix("target area: ").ok_or(ParseError)?;
-        let (x_range, y_range) = interesting_part.split_once(", ").ok_or(ParseError)?;
+        let line = input.trim_end();
+        let expected = "a target area like `target area: x=20..30, y=-10..-5`";
+        let (x_range, y_range) = line
+            .strip_prefix("target area: ")
+            .and_then(|s| s.split_once(", "))
+            .ok_or_else(|| ParseError::at(input, line, expected))?;
         let (x_min, x_max) = x_range
             .strip_prefix("x=")
             .and_then(|s| s.split_once(".."))
-            .ok_or(ParseError)?;
+            .ok_or_else(|| ParseError::at(input, x_range, "a range like `x=20..30`"))?;
         let (y_a, y_b) = y_range
             .strip_prefix("y=")
             .and_then(|s| s.split_once(".."))
-            .ok_or(ParseError)?;
-
-        let y_a: i32 = y_a.parse()?;
-        let y_b: i32 = y_b.parse()?;
+            .ok_or_else(|| ParseError::at(input, y_range, "a range like `y=-10..-5`"))?;
+
+        // The target is always to the right of and below the probe
+        let parse = |coordinate, expected: &str, range: RangeInclusive<i32>| {
+            parse_token(input, coordinate, expected)
+                .ok()
+                .filter(|coordinate| range.contains(coordinate))
+                .ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        coordinate,
+                        format!("{expected} from {} to {}", range.start(), range.end()),
+                    )
+                })
+        };
+        let x_a = parse(x_min, "an x coordinate", 1..=MAX_DISTANCE)?;
+        let x_b = parse(x_max, "an x coordinate", 1..=MAX_DISTANCE)?;
+        let y_a = parse(y_a, "a y coordinate", -MAX_DISTANCE..=-1)?;
+        let y_b = parse(y_b, "a y coordinate", -MAX_DISTANCE..=-1)?;
 
         Ok(Self {
-            x_min: x_min.parse()?,
-            x_max: x_max.parse()?,
+            x_min: min(x_a, x_b),
+            x_max: max(x_a, x_b),
             y_min: min(y_a, y_b),
             y_max: max(y_a, y_b),
         })
@@ -76,8 +111,8 @@ impl CheckTargetArea for (i32, i32) {
 fn find_max_height(target_area: &TargetArea) -> i32 {
     let mut max_height = 0;
 
-    for delta_x in 0..100 {
-        for delta_y in 0..100 {
+    for delta_x in delta_x_range(target_area) {
+        for delta_y in 0..=*delta_y_range(target_area).end() {
             if let Some(height) = check_trajectory(delta_x, delta_y, target_area) {
                 if height > max_height {
                     max_height = height;
@@ -93,7 +128,7 @@ fn find_num_trajectories(target_area: &TargetArea) -> i32 {
     let mut num_trajectories = 0;
 
     for delta_x in delta_x_range(target_area) {
-        for delta_y in -600..600 {
+        for delta_y in delta_y_range(target_area) {
             if check_trajectory(delta_x, delta_y, target_area).is_some() {
                 num_trajectories += 1;
             }
@@ -103,7 +138,7 @@ fn find_num_trajectories(target_area: &TargetArea) -> i32 {
     num_trajectories
 }
 
-fn delta_x_range(target_area: &TargetArea) -> std::ops::RangeInclusive<i32> {
+fn delta_x_range(target_area: &TargetArea) -> RangeInclusive<i32> {
     // let mut from = 1;
     // let mut delta_from = 1;
     // while from < target_area.x_min {
@@ -115,6 +150,12 @@ fn delta_x_range(target_area: &TargetArea) -> std::ops::RangeInclusive<i32> {
     from..=to
 }
 
+// A probe launched upwards comes back down to 0 one step faster than it went
+// up, so any faster and it skips past the target
+fn delta_y_range(target_area: &TargetArea) -> RangeInclusive<i32> {
+    target_area.y_min..=-target_area.y_min - 1
+}
+
 fn check_trajectory(delta_x: i32, delta_y: i32, target_area: &TargetArea) -> Option<i32> {
     let mut x = 0;
     let mut y = 0;
@@ -122,7 +163,7 @@ fn check_trajectory(delta_x: i32, delta_y: i32, target_area: &TargetArea) -> Opt
     let mut delta_x = delta_x;
     let mut delta_y = delta_y;
 
-    while can_reach_target(x, y, delta_x, delta_y, target_area) {
+    while can_reach_target(x, y, delta_x, target_area) {
         if (x, y).is_in(target_area) {
             return Some(max_y);
         }
@@ -142,57 +183,59 @@ fn check_trajectory(delta_x: i32, delta_y: i32, target_area: &TargetArea) -> Opt
     None
 }
 
-fn can_reach_target(x: i32, y: i32, delta_x: i32, delta_y: i32, target_area: &TargetArea) -> bool {
+fn can_reach_target(x: i32, y: i32, delta_x: i32, target_area: &TargetArea) -> bool {
     // y > target_area.y_min && (delta_x > 0 || x > target_area.x_min)
 
-    if delta_x <= 0 && x < target_area.x_min {
-        false
-    } else if y < target_area.y_min {
-        false
-    } else {
-        true
-    }
-}
-
-#[derive(Debug)]
-struct ParseError;
-
-impl From<ParseIntError> for ParseError {
-    fn from(_: ParseIntError) -> Self {
-        Self
-    }
+    !(delta_x <= 0 && x < target_area.x_min || x > target_area.x_max || y < target_area.y_min)
 }
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1("target area: x=20..30, y=-10..-5").unwrap(), 45);
-        assert_eq!(part_1(INPUT).unwrap(), 3_003);
+        assert_eq!(
+            find_max_height(&"target area: x=20..30, y=-10..-5".parse().unwrap()),
+            45
+        );
+        assert_eq!(find_max_height(&INPUT.parse().unwrap()), 3_003);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2("target area: x=20..30, y=-10..-5").unwrap(), 112);
-        assert_eq!(part_2(INPUT).unwrap(), 940);
+        assert_eq!(
+            find_num_trajectories(&"target area: x=20..30, y=-10..-5".parse().unwrap()),
+            112
+        );
+        assert_eq!(find_num_trajectories(&INPUT.parse().unwrap()), 940);
+        // Further away than the puzzle's, and backwards
+        assert_eq!(
+            find_num_trajectories(&"target area: x=1000..1000, y=-1..-1000".parse().unwrap()),
+            1_303
+        );
     }
 
-    #[bench]
-    #[ignore]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT).unwrap());
-    }
-
-    #[bench]
-    #[ignore]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT).unwrap());
+    #[test]
+    fn test_parse_errors() {
+        let parse_error = |input: &str| input.parse::<TargetArea>().unwrap_err().to_string();
+        assert_eq!(
+            parse_error("target area: x=20..30, y=-10..-5a\n"),
+            "line 1, column 31: expected a y coordinate from -1000 to -1, found `-5a`"
+        );
+        assert_eq!(
+            parse_error("target area: x=1..999999999, y=-10..-5"),
+            "line 1, column 19: expected an x coordinate from 1 to 1000, found `999999999`"
+        );
+        assert_eq!(
+            parse_error("target area: x=20..30, y=5..10"),
+            "line 1, column 26: expected a y coordinate from -1000 to -1, found `5`"
+        );
+        assert_eq!(
+            parse_error("target area: x=20-30, y=-10..-5"),
+            "line 1, column 14: expected a range like `x=20..30`, found `x=20-30`"
+        );
     }
 }
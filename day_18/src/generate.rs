@@ -0,0 +1,44 @@
+use aoc_core::Rng;
+
+/// `size` reduced snailfish numbers (but at least two, to add), nested at most
+/// four pairs deep.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let mut input = String::with_capacity(50 * size);
+    for _ in 0..size {
+        write_pair(rng, 1, &mut input);
+        input.push('\n');
+    }
+    input
+}
+
+fn write_pair(rng: &mut Rng, depth: usize, input: &mut String) {
+    input.push('[');
+    write_element(rng, depth, input);
+    input.push(',');
+    write_element(rng, depth, input);
+    input.push(']');
+}
+
+fn write_element(rng: &mut Rng, depth: usize, input: &mut String) {
+    if depth < 4 && rng.chance(2, 3) {
+        write_pair(rng, depth + 1, input);
+    } else {
+        input.push(char::from(*rng.choose(b"0123456789")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day18;
+    use aoc_core::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 20);
+        assert_eq!(Day18::parse(&input).unwrap().len(), 20);
+        let input = generate(&mut Rng::new(1), 1);
+        assert_eq!(Day18::parse(&input).unwrap().len(), 2);
+    }
+}
@@ -1,39 +1,96 @@
 #![allow(dead_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![feature(int_roundings, test)]
+
+mod generate;
+
+use aoc_core::{parse_lines, Answer, ParseError, Solution, SolveError};
+pub use generate::generate;
 
 use std::{
-    fmt::{Debug, Formatter, Result as FormatResult},
-    iter::Sum,
-    num::ParseIntError,
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FormatResult},
     ops::Add,
     str::FromStr,
 };
 
-fn part_1(input: &str) -> Result<u32, ParseError> {
-    let numbers: Vec<SnailfishNumber> = input.lines().map(str::parse).collect::<Result<_, _>>()?;
-    let sum: SnailfishNumber = numbers.into_iter().sum();
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = Vec<SnailfishNumber>;
+    type Error = SolveError<HomeworkError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(numbers: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_1(numbers).map_err(SolveError::Solve)?.into())
+    }
+
+    fn part_2(numbers: &Self::Input<'_>) -> Result<Answer, Self::Error> {
+        Ok(part_2(numbers).map_err(SolveError::Solve)?.into())
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<SnailfishNumber>, ParseError> {
+    let numbers: Vec<_> = parse_lines(input, str::parse)?;
+    if numbers.is_empty() {
+        let end = input.trim_end().len();
+        return Err(ParseError::at(
+            input,
+            &input[end..end],
+            "a snailfish number",
+        ));
+    }
+    Ok(numbers)
+}
+
+fn part_1(numbers: &[SnailfishNumber]) -> Result<u32, HomeworkError> {
+    let sum = numbers
+        .iter()
+        .cloned()
+        .reduce(Add::add)
+        .ok_or(HomeworkError::TooFewNumbers { needed: 1 })?;
     Ok(sum.magnitude())
 }
 
-fn part_2(input: &str) -> Result<u32, ParseError> {
-    let numbers: Vec<SnailfishNumber> = input.lines().map(str::parse).collect::<Result<_, _>>()?;
-    let max_magnitude = numbers
+fn part_2(numbers: &[SnailfishNumber]) -> Result<u32, HomeworkError> {
+    // Two different numbers are two different lines, even if they're equal
+    numbers
         .iter()
-        .flat_map(|number| {
+        .enumerate()
+        .flat_map(|(index, number)| {
             numbers
                 .iter()
-                .filter(move |other_number| *other_number != number)
-                .map(|other_number| (number.clone() + other_number.clone()).magnitude())
+                .enumerate()
+                .filter(move |&(other_index, _)| other_index != index)
+                .map(|(_, other_number)| (number.clone() + other_number.clone()).magnitude())
         })
         .max()
-        .unwrap();
+        .ok_or(HomeworkError::TooFewNumbers { needed: 2 })
+}
+
+#[derive(Debug)]
+pub enum HomeworkError {
+    /// A part needs more numbers than the homework has.
+    TooFewNumbers { needed: usize },
+}
 
-    Ok(max_magnitude)
+impl Display for HomeworkError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::TooFewNumbers { needed: 1 } => write!(formatter, "there are no numbers to add"),
+            Self::TooFewNumbers { needed } => {
+                write!(formatter, "adding needs at least {needed} numbers")
+            }
+        }
+    }
 }
 
+impl Error for HomeworkError {}
+
 #[derive(Clone, PartialEq)]
-enum SnailfishNumber {
+pub enum SnailfishNumber {
     Single(u32),
     Pair(Box<SnailfishNumber>, Box<SnailfishNumber>),
 }
@@ -113,7 +170,7 @@ impl SnailfishNumber {
                             None,
                         );
                     }
-                };
+                }
 
                 match b.clone().do_explode(depth + 1) {
                     (false, None, _, None) => (false, None, Pair(a, b), None), // Right side did not explode either.
@@ -135,7 +192,7 @@ impl SnailfishNumber {
                     ),
                 }
             }
-            _ => panic!("Depth too high: {}", depth),
+            _ => unreachable!("Adding reduced numbers nests no pair inside {depth} pairs"),
         }
     }
 
@@ -143,13 +200,7 @@ impl SnailfishNumber {
         use SnailfishNumber::{Pair, Single};
 
         match self {
-            Single(n) if n >= 10 => (
-                true,
-                Self::new(
-                    Single(n.unstable_div_floor(2)),
-                    Single(n.unstable_div_ceil(2)),
-                ),
-            ),
+            Single(n) if n >= 10 => (true, Self::new(Single(n / 2), Single(n.div_ceil(2)))),
             Single(n) => (false, Single(n)),
             Pair(a, b) => {
                 let (did_split, split_a) = a.clone().split();
@@ -166,7 +217,7 @@ impl SnailfishNumber {
     fn unwrap_single(self) -> u32 {
         match self {
             Self::Single(n) => n,
-            Self::Pair(_, _) => panic!(),
+            Self::Pair(_, _) => unreachable!("Pairs inside four pairs hold regular numbers"),
         }
     }
 
@@ -195,7 +246,7 @@ impl Add for SnailfishNumber {
 impl Debug for SnailfishNumber {
     fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
         match self {
-            Self::Single(n) => n.fmt(formatter),
+            Self::Single(n) => Debug::fmt(n, formatter),
             Self::Pair(a, b) => {
                 write!(formatter, "[")?;
                 a.fmt(formatter)?;
@@ -212,82 +263,152 @@ impl FromStr for SnailfishNumber {
     type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (number, remaining) = parse_pair_snailfish_number(input)?;
-        if remaining.is_empty() {
-            Ok(number)
-        } else {
-            Err(ParseError)
-        }
+        parse_line(input, REDUCED)
     }
 }
 
-impl Sum for SnailfishNumber {
-    fn sum<I>(numbers: I) -> SnailfishNumber
-    where
-        I: Iterator<Item = SnailfishNumber>,
-    {
-        numbers
-            .reduce(Add::add)
-            .expect("empty iterators are not supported")
+// Puzzle inputs are reduced, so no pair is nested inside four pairs and no
+// regular number is above 9. Adding two of those can't nest deeper than
+// exploding handles, or overflow a magnitude, and parsing them can't overflow
+// the stack.
+const REDUCED: Limits = Limits {
+    max_depth: 3,
+    max_regular_number: 9,
+};
+
+#[derive(Clone, Copy)]
+struct Limits {
+    /// The most pairs a pair can be nested inside.
+    max_depth: usize,
+    max_regular_number: u32,
+}
+
+fn parse_line(input: &str, limits: Limits) -> Result<SnailfishNumber, ParseError> {
+    parse_pair_snailfish_number(input, limits, 0)
+        .and_then(|(number, remaining)| {
+            if remaining.is_empty() {
+                Ok(number)
+            } else {
+                Err(SyntaxError::new(
+                    first_character(remaining),
+                    "the end of the line",
+                ))
+            }
+        })
+        .map_err(|SyntaxError { token, expected }| ParseError::at(input, token, expected))
+}
+
+// The parsers below only see what is left of the line, so they report the
+// token they failed on, which parse_line turns into a position
+struct SyntaxError<'a> {
+    token: &'a str,
+    expected: String,
+}
+
+impl<'a> SyntaxError<'a> {
+    fn new(token: &'a str, expected: impl Into<String>) -> Self {
+        Self {
+            token,
+            expected: expected.into(),
+        }
     }
 }
 
-fn parse_snailfish_number(input: &str) -> Result<(SnailfishNumber, &str), ParseError> {
-    parse_single_snailfish_number(input).or_else(|_| parse_pair_snailfish_number(input))
+type ParseResult<'a, T> = Result<(T, &'a str), SyntaxError<'a>>;
+
+fn first_character(input: &str) -> &str {
+    input
+        .chars()
+        .next()
+        .map_or(&input[..0], |character| &input[..character.len_utf8()])
+}
+
+fn parse_snailfish_number(
+    input: &str,
+    limits: Limits,
+    depth: usize,
+) -> ParseResult<'_, SnailfishNumber> {
+    if input.starts_with('[') {
+        parse_pair_snailfish_number(input, limits, depth)
+    } else {
+        parse_single_snailfish_number(input, limits)
+    }
 }
 
-fn parse_single_snailfish_number(input: &str) -> Result<(SnailfishNumber, &str), ParseError> {
+fn parse_single_snailfish_number(input: &str, limits: Limits) -> ParseResult<'_, SnailfishNumber> {
     let (digits, remaining) = parse_at_least_one_digit(input)?;
-    let number = digits.parse()?;
+    let number = digits
+        .parse()
+        .ok()
+        .filter(|&number| number <= limits.max_regular_number)
+        .ok_or_else(|| {
+            SyntaxError::new(
+                digits,
+                format!("a regular number up to {}", limits.max_regular_number),
+            )
+        })?;
     Ok((SnailfishNumber::Single(number), remaining))
 }
 
-fn parse_pair_snailfish_number(input: &str) -> Result<(SnailfishNumber, &str), ParseError> {
+fn parse_pair_snailfish_number(
+    input: &str,
+    limits: Limits,
+    depth: usize,
+) -> ParseResult<'_, SnailfishNumber> {
+    if depth > limits.max_depth {
+        return Err(SyntaxError::new(
+            first_character(input),
+            format!("a regular number instead of a pair inside {depth} pairs"),
+        ));
+    }
     let (_, input) = parse_character('[', input)?;
-    let (a, input) = parse_snailfish_number(input)?;
+    let (a, input) = parse_snailfish_number(input, limits, depth + 1)?;
     let (_, input) = parse_character(',', input)?;
-    let (b, input) = parse_snailfish_number(input)?;
+    let (b, input) = parse_snailfish_number(input, limits, depth + 1)?;
     let (_, input) = parse_character(']', input)?;
 
     Ok((SnailfishNumber::new(a, b), input))
 }
 
-fn parse_at_least_one_digit(input: &str) -> Result<(&str, &str), ParseError> {
-    let num_digits = input.chars().take_while(|c| c.is_digit(10)).count();
+fn parse_at_least_one_digit(input: &str) -> ParseResult<'_, &str> {
+    let num_digits = input.chars().take_while(char::is_ascii_digit).count();
     if num_digits > 0 {
         Ok((&input[0..num_digits], &input[num_digits..]))
     } else {
-        Err(ParseError)
+        Err(SyntaxError::new(
+            first_character(input),
+            "a regular number or `[`",
+        ))
     }
 }
 
-fn parse_character(character: char, input: &str) -> Result<(char, &str), ParseError> {
+fn parse_character(character: char, input: &str) -> ParseResult<'_, char> {
     if input.starts_with(character) {
         Ok((character, &input[character.len_utf8()..]))
     } else {
-        Err(ParseError)
-    }
-}
-
-#[derive(Debug)]
-struct ParseError;
-
-impl From<ParseIntError> for ParseError {
-    fn from(_: ParseIntError) -> Self {
-        Self
+        Err(SyntaxError::new(
+            first_character(input),
+            format!("`{character}`"),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    extern crate test;
-
     use super::*;
-    use test::Bencher;
 
     const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
     const INPUT: &str = include_str!("input.txt");
 
+    // Numbers part way through being reduced, which puzzle inputs never are
+    fn unreduced(input: &str) -> SnailfishNumber {
+        let limits = Limits {
+            max_depth: 4,
+            max_regular_number: u32::MAX,
+        };
+        parse_line(input, limits).unwrap()
+    }
+
     #[test]
     fn test_addition() {
         let a: SnailfishNumber = "[1,2]".parse().unwrap();
@@ -316,8 +437,8 @@ mod tests {
                 "[[[[0,7],4],[15,[0,13]]],[1,1]]",
             ),
         ] {
-            let number: SnailfishNumber = input.parse().unwrap();
-            let expected: SnailfishNumber = expected.parse().unwrap();
+            let number = unreduced(input);
+            let expected = unreduced(expected);
             assert_eq!(number.explode().1, expected);
         }
     }
@@ -334,8 +455,8 @@ mod tests {
                 "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]",
             ),
         ] {
-            let number: SnailfishNumber = input.parse().unwrap();
-            let expected: SnailfishNumber = expected.parse().unwrap();
+            let number = unreduced(input);
+            let expected = unreduced(expected);
             assert_eq!(number.split().1, expected);
         }
     }
@@ -350,25 +471,73 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(SAMPLE_INPUT).unwrap(), 4_140);
-        assert_eq!(part_1(INPUT).unwrap(), 3_551);
+        assert_eq!(part_1(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(), 4_140);
+        assert_eq!(part_1(&parse_input(INPUT).unwrap()).unwrap(), 3_551);
+        assert_eq!(part_1(&parse_input("[1,2]\n").unwrap()).unwrap(), 7);
+        let deepest = "[[[[9,9],[9,9]],[[9,9],[9,9]]],[[[9,9],[9,9]],[[9,9],[9,9]]]]\n";
+        assert_eq!(
+            part_1(&parse_input(&deepest.repeat(2)).unwrap()).unwrap(),
+            4_037
+        );
+        assert_eq!(
+            part_1(&[]).unwrap_err().to_string(),
+            "there are no numbers to add"
+        );
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(SAMPLE_INPUT).unwrap(), 3_993);
-        assert_eq!(part_2(INPUT).unwrap(), 4_555);
+        assert_eq!(part_2(&parse_input(SAMPLE_INPUT).unwrap()).unwrap(), 3_993);
+        assert_eq!(part_2(&parse_input(INPUT).unwrap()).unwrap(), 4_555);
+        assert_eq!(part_2(&parse_input("[1,2]\n[1,2]\n").unwrap()).unwrap(), 35);
+        assert_eq!(
+            part_2(&parse_input("[1,2]\n").unwrap())
+                .unwrap_err()
+                .to_string(),
+            "adding needs at least 2 numbers"
+        );
     }
 
-    #[bench]
-    #[ignore]
-    fn bench_part_1(b: &mut Bencher) {
-        b.iter(|| part_1(INPUT).unwrap());
-    }
-
-    #[bench]
-    #[ignore]
-    fn bench_part_2(b: &mut Bencher) {
-        b.iter(|| part_2(INPUT).unwrap());
+    #[test]
+    fn test_parse_errors() {
+        let parse_error = |input: &str| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            parse_error("[1,2]\n[[3,4];5]\n"),
+            "line 2, column 7: expected `,`, found `;`"
+        );
+        assert_eq!(
+            parse_error("[[1,2],x]"),
+            "line 1, column 8: expected a regular number or `[`, found `x`"
+        );
+        assert_eq!(
+            parse_error("[1,2]]"),
+            "line 1, column 6: expected the end of the line, found `]`"
+        );
+        assert_eq!(
+            parse_error("[1,2"),
+            "line 1, column 5: expected `]`, found nothing"
+        );
+        assert_eq!(
+            parse_error(""),
+            "line 1, column 1: expected a snailfish number, found nothing"
+        );
+        assert_eq!(
+            parse_error("[[[[[1,2],3],4],5],6]\n[1,1]"),
+            "line 1, column 5: expected a regular number instead of a pair inside 4 pairs, \
+             found `[`"
+        );
+        assert_eq!(
+            parse_error(&"[".repeat(300_000)),
+            "line 1, column 5: expected a regular number instead of a pair inside 4 pairs, \
+             found `[`"
+        );
+        assert_eq!(
+            parse_error("[4294967295,1]"),
+            "line 1, column 2: expected a regular number up to 9, found `4294967295`"
+        );
+        assert_eq!(
+            parse_error("[10,1]"),
+            "line 1, column 2: expected a regular number up to 9, found `10`"
+        );
     }
 }